@@ -1,28 +1,55 @@
 //std library
 use std::collections::VecDeque;
+use std::time::Duration;
 
-use crate::components::enums::ReloadAmount;
-use crate::components::shotgun;
+use crate::components::ai::{self, Move};
+use crate::components::enums::{ConfirmAction, Menu, Phase, ReloadAmount, TimeoutAction, TitlePage};
+use crate::components::items::Items;
+use crate::components::player::Player;
+use crate::components::mode::{ItemPool, Rounds, CUSTOM, STAGE_PRIZE};
+use crate::components::setup::{self, GameSetup, MAX_SEED, MAX_START_HEALTH};
+use crate::components::shotgun::Shell;
+use crate::uihelp::animation::{self, Animator};
+use crate::uihelp::effects;
+use crate::uihelp::window::{self, Drag};
+use crate::uihelp::confirm::Confirm;
+use crate::uihelp::inspector::{self, Cheat, Inspector};
+use crate::uihelp::log_view::{LogEntry, LogView};
+use crate::uihelp::pause::{self, MenuAction, PauseItem, PauseMenu, SettingsRow};
+use crate::uihelp::title::{self as title_menu, ReplayRow, SetupRow, TitleItem, TitleMenu};
+use crate::uihelp::pool_editor::{PoolEditor, PoolRow};
+use crate::uihelp::text;
+use crate::util;
+use crate::uihelp::redraw::Redraw;
+use crate::uihelp::registry;
+use crate::uihelp::screen::ScreenSize;
 use crate::uihelp::widget_data::{WidgetData, WidgetKind};
 use crate::ui;
 use crate::components::match_data::MatchData;
+use crate::components::turn_timer::TurnTimer;
 
 use crate::event::{AppEvent, Event, EventHandler};
-use crossterm::event::EnableMouseCapture;
+use crossterm::event::{DisableFocusChange, DisableMouseCapture, EnableFocusChange, EnableMouseCapture};
+use rand::{rngs::StdRng, Rng, SeedableRng};
 use ratatui::{
-    layout::{Constraint, Direction, Layout, Rect},
     /* style::{ Color, Style, Stylize },
     widgets::{Block, Borders, Clear, Paragraph, Wrap, BorderType}, */
     Frame,
     DefaultTerminal,
-    crossterm::event::{KeyCode, KeyEvent, KeyModifiers, MouseEvent, MouseButton, MouseEventKind, self},
+    crossterm::event::{KeyCode, KeyEvent, KeyModifiers, MouseEvent, MouseButton, MouseEventKind},
 };
 
-//crossterm
-use crossterm::terminal::{enable_raw_mode, disable_raw_mode};
-
 //user made ones
 use crate::data::Data;
+use crate::settings::{self, Settings, DEFAULT_GRACE};
+use crate::keymap::{KeyAction, Keymap};
+use crate::theme::Palette;
+use crate::save::{self, SaveGame};
+use crate::assets::{AssetPack, Assets};
+use crate::catalog::{self, Catalog};
+
+/// How long a computer player waits before each move, so people can follow along.
+const AI_DELAY: Duration = Duration::from_secs(1);
 
 /// Application.
 #[derive(Debug)]
@@ -40,9 +67,43 @@ pub struct App {
     ///holds the information of the widgets
     pub widget_data: WidgetData,
     /// log for popup texts
-    pub log: VecDeque<String>,
-    ///Where is the log scrolled to
-    pub log_scroll: u16,
+    pub log: VecDeque<LogEntry>,
+    ///Where is the log scrolled to and what is searched
+    pub log_view: LogView,
+    /// timed content swaps on widgets
+    pub animator: Animator,
+    /// player preferences
+    pub settings: Settings,
+    /// art, icons and flavour text
+    pub assets: Assets,
+    /// panel being moved or resized with the mouse
+    pub drag: Option<Drag>,
+    /// the question the confirm dialog is asking
+    pub confirm: Option<Confirm>,
+    /// selected inventory slot
+    pub inventory_selected: usize,
+    /// opponent picked on the player board, the next one alive when not set
+    pub target: Option<usize>,
+    /// what is open in the data inspector
+    pub inspector: Inspector,
+    /// how much room the terminal has, updated on every draw and resize
+    pub screen: ScreenSize,
+    /// whether the screen needs drawing again
+    pub redraw: Redraw,
+    /// does the terminal have focus, ticks are paused while it doesn't
+    pub focused: bool,
+    /// how long the active player has left
+    pub turn_timer: TurnTimer,
+    /// the pause menu, while it is open
+    pub pause: Option<PauseMenu>,
+    /// the title screen, while it is open
+    pub title: Option<TitleMenu>,
+    /// dice for shells and items, seeded when the match was given a seed
+    pub rng: StdRng,
+    /// ticks the computer player has been thinking
+    pub ai_wait: u32,
+    /// shell pools and game modes
+    pub catalog: Catalog,
 }
 
 impl Default for App {
@@ -54,8 +115,25 @@ impl Default for App {
             data: Data::new(),
             match_data: MatchData::new(),
             log: VecDeque::new(),
-            log_scroll: 0,
+            log_view: LogView::new(),
             widget_data: WidgetData::new(),
+            animator: Animator::new(),
+            settings: Settings::new(),
+            assets: Assets::new(),
+            drag: None,
+            confirm: None,
+            inventory_selected: 0,
+            target: None,
+            inspector: Inspector::new(),
+            screen: ScreenSize::default(),
+            redraw: Redraw::new(),
+            focused: true,
+            turn_timer: TurnTimer::default(),
+            pause: None,
+            title: None,
+            rng: StdRng::from_entropy(),
+            ai_wait: 0,
+            catalog: Catalog::builtin(),
         }
     }
 }
@@ -66,164 +144,240 @@ impl App {
         Self::default()
     }
 
+    pub fn with_settings(settings: Settings) -> Self {
+        let mut app = Self {
+            settings,
+            ..Self::default()
+        };
+        if let Some(path) = settings::preferences_path() {
+            match settings::load_preferences(&path) {
+                Ok(preferences) => app.settings.preferences = preferences,
+                Err(err) => app.send_log(Some(format!("Could not load settings: {err}"))),
+            }
+        }
+        app.apply_rates();
+        let grace = app.settings.grace.unwrap_or(DEFAULT_GRACE);
+        app.turn_timer = TurnTimer::new(app.settings.turn_time, grace, app.events.rates().tick_fps);
+        if app.turn_timer.is_enabled() {
+            app.widget_data.open(WidgetKind::Clock);
+        }
+        app.animator.speed = app.settings.preferences.animation_speed;
+        if let Some(dir) = app.settings.pack.clone()
+            && let Err(err) = app.assets.load_pack(&dir)
+        {
+            app.send_log(Some(format!("Could not load asset pack: {err}")));
+        }
+        if let Some(path) = window::layout_path()
+            && let Err(err) = window::load_layout(&path, &app.widget_data)
+        {
+            app.send_log(Some(format!("Could not load panel layout: {err}")));
+        }
+        //a broken data file keeps the built-in pools and modes, the title screen says why
+        let catalog_error = settings::config_dir()
+            .and_then(|dir| app.catalog.load_dir(&dir).err())
+            .map(|err| format!("Could not load pools and modes: {err:#}"));
+        app.open_title();
+        if let Some(msg) = catalog_error {
+            if let Some(title) = app.title.as_mut() {
+                title.notice = Some(msg.clone());
+            }
+            app.send_log(Some(msg));
+        }
+        app
+    }
+
+    /// The asset pack for the shotgun that is in play.
+    pub fn pack(&self) -> &AssetPack {
+        self.assets.pack(&self.data.shotgun.model)
+    }
+
     pub fn send_log(&mut self, message: Option<String>) {
         if let Some(msg) = message {
             let max_size: usize = 1000;
             if self.log.len() >= max_size {
                 self.log.pop_front();
             }
-            self.log.push_back(msg)
+            self.log.push_back(LogEntry { round: self.match_data.count(), text: msg });
+            //messages can come out of a draw, so they ask for the next one themselves
+            self.redraw.request();
         }
     }
 
     /// Run the application's main loop.
     pub async fn run(mut self, mut terminal: DefaultTerminal) -> color_eyre::Result<()> {
         crossterm::terminal::enable_raw_mode()?;
-        crossterm::execute!(std::io::stdout(), EnableMouseCapture)?;
+        crossterm::execute!(std::io::stdout(), EnableMouseCapture, EnableFocusChange)?;
 
         while self.running {
-            terminal.draw(|frame| self.render_ui(frame))?;
-            match self.events.next().await? {
+            let event = self.events.next().await?;
+            //ticks ask for a frame themselves, moving the mouse around changes nothing
+            match &event {
+                Event::Tick | Event::Frame => {},
+                Event::Crossterm(crossterm::event::Event::Mouse(mouse)) if mouse.kind == MouseEventKind::Moved => {},
+                _ => self.redraw.request(),
+            }
+            match event {
                 Event::Tick => self.tick(),
+                //a burst of input between two frames is drawn once
+                Event::Frame => {
+                    if self.redraw.take() {
+                        terminal.draw(|frame| self.render_ui(frame))?;
+                    }
+                },
                 Event::Crossterm(event) => match event {
                     crossterm::event::Event::Key(key_event) => self.handle_key_events(key_event)?,
                     crossterm::event::Event::Mouse(mouse_event) => self.handle_mouse_events(mouse_event)?,
+                    crossterm::event::Event::Resize(width, height) => self.resize(width, height),
+                    crossterm::event::Event::FocusLost => self.set_focused(false),
+                    crossterm::event::Event::FocusGained => self.set_focused(true),
                     _ => {}
                 },
                 Event::App(app_event) => match app_event {
                     AppEvent::Quit => self.quit(),
-                    AppEvent::Reload(amount) => {
-                        self.data.shotgun.load_random_shells(amount.as_usize());
+                    AppEvent::Reload(amount) => self.reload(amount),
+                    //the computer plays its own turns
+                    AppEvent::Shoot | AppEvent::ShootSelf | AppEvent::UseItem(_)
+                    | AppEvent::SelectTarget(_) | AppEvent::NextTarget if self.ai_playing() => {},
+                    AppEvent::Shoot => self.shoot(false),
+                    AppEvent::ShootSelf => self.shoot(true),
+                    AppEvent::Forfeit => {
+                        self.reset_match();
+                        self.send_log(Some("You forfeit the match.".to_string()));
                     },
-                    AppEvent::Shoot => {
-                        if let Some(msg) = self.data.shotgun.shoot() {
-                            if self.data.shotgun.shell_count() == 0 {
-                                self.match_data.incr_round();
-                            } else {
-                                //make it so that the widget_data has shotgun shooting
-                                //for .25 seconds
-                                let widget_data = self.widget_data.clone(); // clone for async move
-                                tokio::spawn(async move {
-                                    widget_data.change_content(WidgetKind::Shotgun, Some("💥 Shooting!".into()));
-                                    tokio::time::sleep(std::time::Duration::from_millis(250)).await;
-                                    widget_data.change_content(WidgetKind::Shotgun, None);
-                                });
-                            }
-                           self.send_log(Some(msg));
-                        }
+                    AppEvent::TurnTimeout => self.turn_timeout(),
+                    AppEvent::Pause => {
+                        self.pause = Some(PauseMenu::new());
+                        self.widget_data.open(WidgetKind::Pause);
+                        self.sync_ticks();
                     },
-                    //TODO: I need to make it so that data popsup the first time I press d
-                    AppEvent::ShowData => {
-                        if self.widget_data.is_displayed(WidgetKind::Data) {
-                            self.widget_data.set_widget(WidgetKind::Data, false, false);
-                            self.widget_data
-                                .render_stack
-                                .retain(|k| *k != WidgetKind::Data);
-                            self.widget_data.focus_next();
-                            if let Some(first) = self.widget_data.render_stack.first().cloned() {
-                                self.widget_data.kind_focus(&first);
-                            }
-                        } else {
-                            self.widget_data.set_widget(WidgetKind::Data, true, true);
-                            //this is to get the rendering in the right order
-                            self.widget_data.render_stack.push(WidgetKind::Data);
-                        }
+                    AppEvent::Resume => {
+                        self.pause = None;
+                        self.widget_data.close(WidgetKind::Pause);
+                        self.sync_ticks();
                     },
-                    AppEvent::ShowLog => {
-                        if self.widget_data.is_displayed(WidgetKind::Log) {
-                            self.widget_data.set_widget(WidgetKind::Log, false, false);
-                            self.widget_data
-                                .render_stack
-                                .retain(|k| *k != WidgetKind::Log);
-                            if let Some(first) = self.widget_data.render_stack.first().cloned() {
-                                self.widget_data.kind_focus(&first);
-                            }
-                        } else {
-                            self.widget_data.set_widget(WidgetKind::Log, true, true);
-                            self.widget_data.render_stack.push(WidgetKind::Log)
-                        }
+                    AppEvent::Menu(action) => self.menu(action),
+                    AppEvent::Title(action) => self.title_action(action),
+                    AppEvent::Ask(action) => {
+                        self.confirm = Some(Confirm::new(action, self.match_data.turn()));
+                        self.widget_data.open(WidgetKind::Confirm);
                     },
-                    AppEvent::ShowInventory => {
-                        if self.widget_data.is_displayed(WidgetKind::Inventory) {
-                            self.widget_data.set_widget(WidgetKind::Inventory, false, false);
-                            self.widget_data
-                                .render_stack
-                                .retain(|k| *k != WidgetKind::Inventory);
-                            if let Some(first) = self.widget_data.render_stack.first().cloned() {
-                                self.widget_data.kind_focus(&first);
-                            }
-                        } else {
-                            self.widget_data.set_widget(WidgetKind::Inventory, true, true);
-                            self.widget_data.render_stack.push(WidgetKind::Inventory)
+                    AppEvent::Answer(action, accepted) => {
+                        let asked = self.confirm.take().map(|confirm| confirm.turn);
+                        self.widget_data.close(WidgetKind::Confirm);
+                        //an answer for a turn that is already over counts as no
+                        let accepted = accepted && asked == Some(self.match_data.turn());
+                        match (action, accepted) {
+                            (ConfirmAction::QuitToTitle, true) => self.quit_to_title(),
+                            (ConfirmAction::OverwriteSave, true) => self.save(true),
+                            (ConfirmAction::ShootSelf, true) => self.events.send(AppEvent::ShootSelf),
+                            (ConfirmAction::Forfeit, true) => self.events.send(AppEvent::Forfeit),
+                            (ConfirmAction::DoubleOrNothing, true) => self.next_stage(),
+                            (ConfirmAction::DoubleOrNothing, false) => self.cash_out(),
+                            (_, false) => {},
                         }
                     },
-                    AppEvent::ShowPlayer => {
-                        if self.widget_data.is_displayed(WidgetKind::Player) {
-                            self.widget_data.set_widget(WidgetKind::Player, false, false);
-                            self.widget_data
-                                .render_stack
-                                .retain(|k| *k != WidgetKind::Player);
-                            if let Some(first) = self.widget_data.render_stack.first().cloned() {
-                                self.widget_data.kind_focus(&first);
-                            }
-                        } else {
-                            self.widget_data.set_widget(WidgetKind::Player, true, true);
-                            self.widget_data.render_stack.push(WidgetKind::Player)
-                        }
+                    AppEvent::ToggleWidget(kind) => {
+                        self.widget_data.toggle(kind);
+                    },
+                    AppEvent::CloseWidget(kind) => {
+                        self.widget_data.close(kind);
+                    },
+                    AppEvent::FocusWidget(kind) => {
+                        self.widget_data.focus(kind);
+                    },
+                    AppEvent::LowerWidget(kind) => {
+                        self.widget_data.lower(kind);
                     },
                     AppEvent::FocusShotgun => {
                         self.widget_data.toggle_focus(WidgetKind::Shotgun);
                     },
-                    AppEvent::ScrollUp => {
-                        if self.log_scroll > 0 {
-                            self.log_scroll -= 1;
-                        }
+                    AppEvent::SelectItem(slot) => {
+                        let slots = self.active_player().map_or(0, |player| player.slots().len());
+                        self.inventory_selected = slot.min(slots.saturating_sub(1));
                     },
-                    AppEvent::ScrollDown => {
-                        self.log_scroll += 1;
+                    AppEvent::UseItem(slot) => self.use_item(slot),
+                    AppEvent::SelectTarget(idx) => self.select_target(idx),
+                    AppEvent::NextTarget => self.next_target(),
+                    AppEvent::Inspect(action) => {
+                        let rows = self.inspector.rows(&inspector::tree(&self));
+                        let height = self.widget_data.area(WidgetKind::Data).map_or(1, |area| area.height.saturating_sub(2));
+                        self.inspector.apply(action, &rows, height as usize);
+                    },
+                    AppEvent::Cheat(cheat) if self.settings.cheats => self.cheat(cheat),
+                    AppEvent::Cheat(_) => {},
+                    AppEvent::Log(action) => {
+                        let lines = LogView::lines(&self.log);
+                        let area = self.widget_data.area(WidgetKind::Log).unwrap_or_default();
+                        let (width, height) = (area.width.saturating_sub(2), area.height.saturating_sub(2));
+                        self.log_view.apply(action, &lines, width, height);
                     },
                     AppEvent::ChangeFocus => {
-                        self.widget_data.focus_next();
+                        self.widget_data.cycle_focus(true);
                     },
                     AppEvent::ChangeFocusBack => {
-                        self.widget_data.focus_prev();
-                    },
-                    _ => {
-                        self.send_log(Some(String::from("Failure to catch event")));
+                        self.widget_data.cycle_focus(false);
                     },
                 },
             }
         }
+        crossterm::execute!(std::io::stdout(), DisableMouseCapture, DisableFocusChange)?;
         Ok(())
     }
 
     /// Handles the key events and updates the state of [`App`].
     pub fn handle_key_events(&mut self, key_event: KeyEvent) -> color_eyre::Result<()> {
+        //nothing is on screen to act on, only let the player leave
+        if self.screen == ScreenSize::TooSmall {
+            if key_event.code == KeyCode::Char('c') && key_event.modifiers == KeyModifiers::CONTROL {
+                self.events.send(AppEvent::Quit);
+            }
+            return Ok(());
+        }
+
+        //the focused widget gets the first look at the key
+        if let Some(kind) = self.widget_data.focused()
+            && let Some(on_key) = registry::spec(kind).on_key
+            && on_key(self, &key_event)
+        {
+            return Ok(());
+        }
+
+        //a modal only lets go with Esc
+        if let Some(modal) = self.widget_data.modal() {
+            match key_event.code {
+                KeyCode::Esc => self.events.send(AppEvent::CloseWidget(modal)),
+                KeyCode::Char('c') if key_event.modifiers == KeyModifiers::CONTROL => {
+                    self.events.send(AppEvent::Quit)
+                }
+                _ => {}
+            }
+            return Ok(());
+        }
+
+        let keymap = &self.settings.preferences.keymap;
         match key_event.code {
-            KeyCode::Esc | KeyCode::Char('q') => self.events.send(AppEvent::Quit),
+            KeyCode::Esc => self.events.send(AppEvent::Pause),
             KeyCode::Char('c') if key_event.modifiers == KeyModifiers::CONTROL => {
                 self.events.send(AppEvent::Quit)
             }
-            KeyCode::Char('d' | 'D') => self.events.send(AppEvent::ShowData),
-            KeyCode::Char('l' | 'L') => self.events.send(AppEvent::ShowLog),
-            //KeyCode::Char('i' | 'I') => self.events.send(AppEvent::ShowInventory),
-            KeyCode::Char('p' | 'P') => self.events.send(AppEvent::ShowPlayer),
-            KeyCode::Char('s' | 'S') => self.events.send(AppEvent::FocusShotgun),
-            KeyCode::Char('k') if self.widget_data.is_focused(WidgetKind::Log) => self.events.send(AppEvent::ScrollUp),
-            KeyCode::Char('j') if self.widget_data.is_focused(WidgetKind::Log) => self.events.send(AppEvent::ScrollDown),
+            KeyCode::Char(key) if registry::by_hotkey(key).is_some() => {
+                if let Some(spec) = registry::by_hotkey(key) {
+                    self.events.send(AppEvent::ToggleWidget(spec.kind))
+                }
+            }
+            KeyCode::Char(key) if keymap.action(key) == Some(KeyAction::Pause) => self.events.send(AppEvent::Pause),
+            KeyCode::Char(key) if keymap.action(key) == Some(KeyAction::FocusShotgun) => self.events.send(AppEvent::FocusShotgun),
+            KeyCode::Char(key) if keymap.action(key) == Some(KeyAction::NextTarget) && self.in_match() => {
+                self.events.send(AppEvent::NextTarget)
+            }
+            KeyCode::Char(key) if keymap.action(key) == Some(KeyAction::Forfeit) && self.in_match() => {
+                self.events.send(AppEvent::Ask(ConfirmAction::Forfeit))
+            }
             KeyCode::Tab if key_event.modifiers == KeyModifiers::CONTROL => self.events.send(AppEvent::ChangeFocusBack),
             KeyCode::Tab => self.events.send(AppEvent::ChangeFocus),
-            KeyCode::Char('r' | 'R') => {
-                match self.match_data.count() {
-                    1 => self.events.send(AppEvent::Reload(ReloadAmount::One)),
-                    2 => self.events.send(AppEvent::Reload(ReloadAmount::Two)),
-                    3 => self.events.send(AppEvent::Reload(ReloadAmount::Three)),
-                    4 => self.events.send(AppEvent::Reload(ReloadAmount::Four)),
-                    5 => self.events.send(AppEvent::Reload(ReloadAmount::Five)),
-                    _ => self.events.send(AppEvent::Reload(ReloadAmount::Five)),
-                }
+            KeyCode::Char(key) if keymap.action(key) == Some(KeyAction::Reload) => {
+                self.events.send(AppEvent::Reload(ReloadAmount::for_round(self.match_data.count())))
             }
-            KeyCode::Char(' ') if self.widget_data.is_focused(WidgetKind::Shotgun)=> self.events.send(AppEvent::Shoot),
             // Other handlers you could add here.
             _ => {}
         }
@@ -231,19 +385,66 @@ impl App {
     }
 
     pub fn handle_mouse_events(&mut self, mouse_event: MouseEvent) -> color_eyre::Result<()> {
+        let (column, row) = (mouse_event.column, mouse_event.row);
+        let hovered = self.widget_data.widget_at(column, row);
+
+        //while a modal is open everything else ignores the mouse
+        if let Some(modal) = self.widget_data.modal()
+            && hovered != Some(modal)
+            && self.drag.is_none()
+        {
+            return Ok(());
+        }
+
+        //grabbing the border of a panel moves or resizes it
+        if let MouseEventKind::Down(MouseButton::Left) = mouse_event.kind
+            && let Some(kind) = hovered
+            && registry::spec(kind).movable
+            && let Some(area) = self.widget_data.area(kind)
+            && let Some(drag) = Drag::begin(kind, area, column, row)
+        {
+            self.drag = Some(drag);
+            self.events.send(AppEvent::FocusWidget(kind));
+            return Ok(());
+        }
+
+        //the widget under the cursor gets the first look at the event
+        if let Some(kind) = hovered
+            && let Some(on_mouse) = registry::spec(kind).on_mouse
+            && let Some(area) = self.widget_data.area(kind)
+            && on_mouse(self, &mouse_event, area)
+        {
+            return Ok(());
+        }
+
         match mouse_event.kind {
-            MouseEventKind::ScrollUp => {
-                self.send_log(Some("scrolling up".to_string()));
-                self.events.send(AppEvent::ScrollUp)
+            MouseEventKind::Down(MouseButton::Left) => {
+                if let Some(kind) = hovered
+                    && registry::spec(kind).focusable
+                {
+                    self.events.send(AppEvent::FocusWidget(kind));
+                }
             },
-            MouseEventKind::Drag(mouse_button) => {
-                match mouse_button {
-                    MouseButton::Left => {
-                        self.send_log(Some("left dragging".to_string()));
-                    },
-                    _ => {
-                        self.send_log(Some("some other dragging".to_string()));
-                    },
+            //right click sends a panel behind the others
+            MouseEventKind::Down(MouseButton::Right) => {
+                if let Some(kind) = hovered
+                    && registry::spec(kind).movable
+                {
+                    self.events.send(AppEvent::LowerWidget(kind));
+                }
+            },
+            MouseEventKind::Drag(MouseButton::Left) => {
+                if let Some(drag) = self.drag {
+                    let bounds = self.widget_data.bounds();
+                    self.widget_data.set_floating(drag.kind, Some(drag.apply(column, row, bounds)));
+                }
+            },
+            MouseEventKind::Up(MouseButton::Left) => {
+                if self.drag.take().is_some()
+                    && let Some(path) = window::layout_path()
+                    && let Err(err) = window::save_layout(&path, &self.widget_data)
+                {
+                    self.send_log(Some(format!("Could not save panel layout: {err}")));
                 }
             },
             _ => {
@@ -252,16 +453,783 @@ impl App {
         Ok(())
     }
 
+    /// Loads the shotgun by the rules of the mode and hands out items, after a decided match
+    /// this starts the rematch.
+    fn reload(&mut self, amount: ReloadAmount) {
+        if self.match_data.phase() == Phase::Over {
+            self.reset_match();
+        }
+        let rules = &self.data.rules;
+        self.data.shotgun.load(&mut self.rng, &rules.pool, amount.as_usize());
+        for player in self.data.players.iter_mut() {
+            player.give_random_items(&mut self.rng, rules.items.items(), rules.items_per_load);
+            player.known.clear();
+        }
+        if self.match_data.turn().is_none() {
+            self.match_data.set_turn(Some(0));
+        }
+        self.match_data.set_phase(Phase::Turn);
+        self.turn_timer.start_round();
+        //a computer player loads on a tick, with no key press to draw it
+        self.redraw.request();
+    }
+
+    /// Empties the shotgun and seats everyone again as the match was set up.
+    fn reset_match(&mut self) {
+        self.data.shotgun.unload();
+        self.data.players = self.data.rules.players(&self.data.setup.seats);
+        self.match_data = MatchData::new();
+        self.target = None;
+        self.turn_timer.stop();
+    }
+
+    /// Leaves the match for the title screen.
+    fn quit_to_title(&mut self) {
+        self.reset_match();
+        self.log.clear();
+        self.events.send(AppEvent::Resume);
+        self.open_title();
+    }
+
+    fn open_title(&mut self) {
+        let has_save = save::save_path().is_some_and(|path| path.exists());
+        self.title = Some(TitleMenu::new(self.data.setup.clone(), has_save));
+        self.widget_data.open(WidgetKind::Title);
+        self.sync_ticks();
+    }
+
+    /// Closes the title screen onto the table.
+    fn close_title(&mut self) {
+        self.title = None;
+        self.widget_data.close(WidgetKind::Title);
+        self.widget_data.open(WidgetKind::Player);
+        self.widget_data.open(WidgetKind::Inventory);
+        self.inventory_selected = 0;
+        self.ai_wait = 0;
+        self.sync_ticks();
+    }
+
+    fn title_action(&mut self, action: MenuAction) {
+        let Some(title) = self.title.as_mut() else {
+            return;
+        };
+        //typing a name or the seed, Enter or Esc finishes it
+        if title.editing && title.page == TitlePage::PoolEditor {
+            let editor = &mut title.editor;
+            match action {
+                MenuAction::Type(key) => editor.type_name(key),
+                MenuAction::Erase => editor.erase_name(),
+                MenuAction::Select | MenuAction::Back => title.editing = false,
+                _ => {},
+            }
+            return;
+        }
+        if title.editing && title.page == TitlePage::Replays {
+            match action {
+                MenuAction::Type(key) => title.replay_seed = setup::type_seed(title.replay_seed, key),
+                MenuAction::Erase => title.replay_seed = setup::erase_seed(title.replay_seed),
+                MenuAction::Select | MenuAction::Back => title.editing = false,
+                _ => {},
+            }
+            return;
+        }
+        if title.editing {
+            let setup = &mut title.setup;
+            match (SetupRow::all(setup)[title.selected], action) {
+                (SetupRow::Seat(idx), MenuAction::Type(key)) => {
+                    let name = &mut setup.seats[idx].name;
+                    if name.chars().count() < title_menu::MAX_NAME_LEN {
+                        name.push(key);
+                    }
+                },
+                (SetupRow::Seat(idx), MenuAction::Erase) => {
+                    setup.seats[idx].name.pop();
+                },
+                (SetupRow::Seed, MenuAction::Type(key)) => setup.seed = setup::type_seed(setup.seed, key),
+                (SetupRow::Seed, MenuAction::Erase) => setup.seed = setup::erase_seed(setup.seed),
+                (row, MenuAction::Select | MenuAction::Back) => {
+                    title.editing = false;
+                    if let SetupRow::Seat(idx) = row
+                        && setup.seats[idx].name.trim().is_empty()
+                    {
+                        setup.seats[idx].name = format!("Player {}", idx + 1);
+                    }
+                },
+                _ => {},
+            }
+            return;
+        }
+
+        let len = title.rows();
+        match (title.page, action) {
+            (_, MenuAction::Up) => title.selected = (title.selected + len - 1) % len,
+            (_, MenuAction::Down) => title.selected = (title.selected + 1) % len,
+            (_, MenuAction::Point(row, clicked)) => {
+                title.selected = row.min(len - 1);
+                if clicked {
+                    self.title_select();
+                }
+            },
+            (_, MenuAction::Select) => self.title_select(),
+            (TitlePage::NewGame, MenuAction::Back) => title.back(),
+            (TitlePage::NewGame, MenuAction::Left) => self.change_setup(false),
+            (TitlePage::NewGame, MenuAction::Right) => self.change_setup(true),
+            (TitlePage::PoolEditor, MenuAction::Back) => title.back(),
+            (TitlePage::PoolEditor, MenuAction::Left) => self.change_pool(false),
+            (TitlePage::PoolEditor, MenuAction::Right) => self.change_pool(true),
+            (TitlePage::Replays, MenuAction::Back) => title.back(),
+            _ => {},
+        }
+    }
+
+    /// Presses the selected entry of the title screen.
+    fn title_select(&mut self) {
+        let Some(title) = self.title.as_mut() else {
+            return;
+        };
+        match title.page {
+            TitlePage::Main => match TitleItem::ALL[title.selected] {
+                TitleItem::NewGame => title.open(TitlePage::NewGame),
+                TitleItem::Continue if title.has_save => self.continue_game(),
+                TitleItem::Continue => title.notice = Some(String::from("There is no saved game.")),
+                //everyone at the same keyboard
+                TitleItem::Multiplayer => {
+                    for seat in title.setup.seats.iter_mut() {
+                        seat.ai = false;
+                    }
+                    title.open(TitlePage::NewGame);
+                },
+                //starts from the last match played
+                TitleItem::Replays => {
+                    title.replay_seed = self.data.setup.seed;
+                    title.open(TitlePage::Replays);
+                },
+                TitleItem::ShellPools => {
+                    title.editor = PoolEditor::new();
+                    title.open(TitlePage::PoolEditor);
+                },
+                TitleItem::Settings => {
+                    self.pause = Some(PauseMenu::settings());
+                    self.widget_data.open(WidgetKind::Pause);
+                },
+                TitleItem::Quit => self.events.send(AppEvent::Quit),
+            },
+            TitlePage::NewGame => match SetupRow::all(&title.setup)[title.selected] {
+                SetupRow::Seat(_) | SetupRow::Seed => title.editing = true,
+                SetupRow::Start => self.new_game(),
+                SetupRow::Back => title.back(),
+                _ => self.change_setup(true),
+            },
+            TitlePage::PoolEditor => match PoolRow::all()[title.selected].clone() {
+                PoolRow::Name => title.editing = true,
+                PoolRow::Shell(_) => title.editor.next_field(),
+                PoolRow::Save => self.save_pool(),
+                PoolRow::Back => title.back(),
+                _ => self.change_pool(true),
+            },
+            TitlePage::Replays => match ReplayRow::ALL[title.selected] {
+                ReplayRow::Seed => title.editing = true,
+                ReplayRow::Play => self.replay(),
+                ReplayRow::Back => title.back(),
+            },
+        }
+    }
+
+    /// Steps the selected option of the pool editor forward or back.
+    fn change_pool(&mut self, forward: bool) {
+        let Some(title) = self.title.as_mut() else {
+            return;
+        };
+        let editor = &mut title.editor;
+        match PoolRow::all()[title.selected].clone() {
+            //new, then every known pool
+            PoolRow::Load => {
+                let len = self.catalog.pools.len() + 1;
+                let idx = editor.base.map_or(0, |base| base + 1);
+                let idx = if forward { (idx + 1) % len } else { (idx + len - 1) % len };
+                match idx.checked_sub(1) {
+                    Some(base) => editor.load(&self.catalog.pools[base], base),
+                    None => *editor = PoolEditor::new(),
+                }
+            },
+            PoolRow::GuaranteedBlank => editor.pool.guaranteed_blank = !editor.pool.guaranteed_blank,
+            PoolRow::Shell(shell) => editor.step(&shell, forward),
+            _ => {},
+        }
+    }
+
+    /// Checks the pool in the editor and writes it to `pools.toml`, so custom games can use it.
+    fn save_pool(&mut self) {
+        let Some(title) = self.title.as_mut() else {
+            return;
+        };
+        let pool = title.editor.finished();
+        if pool.name.is_empty() {
+            title.notice = Some(String::from("The pool needs a name."));
+            return;
+        }
+        if let Err(err) = pool.validate() {
+            title.notice = Some(err);
+            return;
+        }
+        //a pool named like a built-in one would quietly replace it
+        if catalog::is_builtin_pool(&pool.id) {
+            title.notice = Some(format!("'{}' comes with the game, save it under another name.", pool.name));
+            return;
+        }
+        let notice = match catalog::pools_path().map(|path| catalog::save_pool(&path, &pool)) {
+            Some(Ok(())) => format!("Saved '{}', pick it in a custom game.", pool.name),
+            Some(Err(err)) => format!("Could not save the pool: {err}"),
+            None => format!("'{}' is kept until the game closes.", pool.name),
+        };
+        title.notice = Some(notice);
+        title.editor.pool = pool.clone();
+        self.catalog.set_pool(pool);
+    }
+
+    /// Steps the selected option of the New Game wizard forward or back.
+    fn change_setup(&mut self, forward: bool) {
+        let Some(title) = self.title.as_mut() else {
+            return;
+        };
+        let setup = &mut title.setup;
+        match SetupRow::all(setup)[title.selected] {
+            SetupRow::Mode => {
+                let ids = self.catalog.mode_ids();
+                setup.mode = util::cycle(&ids, setup.mode.as_str(), forward).to_string();
+            },
+            SetupRow::Players if forward => setup.set_player_count(setup.seats.len() + 1),
+            SetupRow::Players => setup.set_player_count(setup.seats.len().saturating_sub(1)),
+            SetupRow::Seat(idx) => setup.seats[idx].ai = !setup.seats[idx].ai,
+            //the other modes bring their own health and pools
+            SetupRow::Health if setup.mode == CUSTOM => {
+                let health = setup.health();
+                setup.set_health(if forward { health % MAX_START_HEALTH + 1 } else { (health + MAX_START_HEALTH - 2) % MAX_START_HEALTH + 1 })
+            },
+            SetupRow::Shells if setup.mode == CUSTOM => {
+                let ids: Vec<&str> = self.catalog.pools.iter().map(|pool| pool.id.as_str()).collect();
+                let pool = util::cycle(&ids, setup.shells(), forward).to_string();
+                setup.set_shells(&pool)
+            },
+            SetupRow::Items if setup.mode == CUSTOM => {
+                setup.set_items(util::cycle(&ItemPool::ALL, setup.items(), forward))
+            },
+            _ => {},
+        }
+    }
+
+    /// Starts the match picked in the New Game wizard.
+    fn new_game(&mut self) {
+        let Some(title) = self.title.as_ref() else {
+            return;
+        };
+        self.start_match(title.setup.clone());
+    }
+
+    /// Starts the New Game picks again with the seed typed on the Replays page.
+    fn replay(&mut self) {
+        let Some(title) = self.title.as_mut() else {
+            return;
+        };
+        let Some(seed) = title.replay_seed else {
+            title.notice = Some(String::from("Type the seed of the match to play again."));
+            return;
+        };
+        let mut setup = title.setup.clone();
+        setup.seed = Some(seed);
+        self.start_match(setup);
+    }
+
+    /// Deals out a match, one without a seed gets a random one so it can be replayed.
+    fn start_match(&mut self, mut setup: GameSetup) {
+        let seed = *setup.seed.get_or_insert_with(|| rand::thread_rng().gen_range(0..=MAX_SEED));
+        self.rng = StdRng::seed_from_u64(seed);
+        let rules = self.catalog.rules(&setup);
+        let msg = format!("A new {} match begins, seed {seed}.", rules.name);
+        self.data = Data::from_setup(setup, rules);
+        self.match_data = MatchData::new();
+        self.turn_timer.stop();
+        self.log.clear();
+        self.close_title();
+        self.send_log(Some(msg));
+    }
+
+    /// Picks the saved match back up where it was left.
+    fn continue_game(&mut self) {
+        let Some(path) = save::save_path() else {
+            return;
+        };
+        let save = match save::load_game(&path) {
+            Ok(save) => save,
+            Err(err) => {
+                if let Some(title) = self.title.as_mut() {
+                    title.notice = Some(format!("Could not load the save: {err}"));
+                }
+                return;
+            },
+        };
+        self.data = save.data;
+        self.match_data = save.match_data;
+        self.log = save.log.into();
+        self.rng = StdRng::from_entropy();
+        self.turn_timer.stop();
+        if self.match_data.phase() == Phase::Turn {
+            self.turn_timer.start_turn();
+        }
+        self.close_title();
+        self.send_log(Some(String::from("Game loaded.")));
+    }
+
+    fn menu(&mut self, action: MenuAction) {
+        let Some(menu) = self.pause.as_mut() else {
+            return;
+        };
+        //waiting for a new key, whatever comes next is it
+        if let Some(key_action) = menu.binding.take() {
+            if let MenuAction::Bind(key) = action {
+                let keymap = &mut self.settings.preferences.keymap;
+                match keymap.bind(key_action, key) {
+                    Ok(()) => self.save_preferences(),
+                    Err(err) => menu.notice = Some(format!("Can't use '{}': {err}", Keymap::label(key))),
+                }
+            }
+            return;
+        }
+
+        let len = menu.rows();
+        match (menu.page, action) {
+            (Menu::Rules, MenuAction::Up) => menu.scroll = menu.scroll.saturating_sub(1),
+            (Menu::Rules, MenuAction::Down) => {
+                let area = menu.list(self.widget_data.area(WidgetKind::Pause).unwrap_or_default()).area;
+                let max = text::max_scroll(text::wrapped_height(pause::RULES, area.width), area.height);
+                menu.scroll = (menu.scroll + 1).min(max);
+            },
+            (Menu::Rules, MenuAction::Select | MenuAction::Back) => menu.back(),
+            (Menu::Rules, _) => {},
+            (_, MenuAction::Up) => menu.selected = (menu.selected + len - 1) % len,
+            (_, MenuAction::Down) => menu.selected = (menu.selected + 1) % len,
+            (_, MenuAction::Point(row, clicked)) => {
+                menu.selected = row.min(len - 1);
+                if clicked {
+                    self.menu_select();
+                }
+            },
+            (_, MenuAction::Select) => self.menu_select(),
+            (Menu::Pause, MenuAction::Back) => self.events.send(AppEvent::Resume),
+            (_, MenuAction::Back) if menu.from_title => self.events.send(AppEvent::Resume),
+            (_, MenuAction::Back) => menu.back(),
+            (_, MenuAction::Left) => self.change_setting(false),
+            (_, MenuAction::Right) => self.change_setting(true),
+            (_, MenuAction::Bind(_) | MenuAction::Type(_) | MenuAction::Erase) => {},
+        }
+    }
+
+    /// Presses the selected entry of the pause menu.
+    fn menu_select(&mut self) {
+        let Some(menu) = self.pause.as_mut() else {
+            return;
+        };
+        match menu.page {
+            Menu::Pause => match PauseItem::ALL[menu.selected] {
+                PauseItem::Resume => self.events.send(AppEvent::Resume),
+                PauseItem::Settings => menu.open(Menu::Settings),
+                PauseItem::Save => self.save(false),
+                PauseItem::Rules => menu.open(Menu::Rules),
+                PauseItem::QuitToTitle if self.in_match() => self.events.send(AppEvent::Ask(ConfirmAction::QuitToTitle)),
+                PauseItem::QuitToTitle => self.quit_to_title(),
+            },
+            Menu::Settings => match SettingsRow::all()[menu.selected] {
+                SettingsRow::Key(action) => {
+                    menu.binding = Some(action);
+                    menu.notice = None;
+                },
+                SettingsRow::Back if menu.from_title => self.events.send(AppEvent::Resume),
+                SettingsRow::Back => menu.back(),
+                _ => self.change_setting(true),
+            },
+            Menu::Rules => menu.back(),
+        }
+    }
+
+    /// Steps the selected setting forward or back and keeps it for next time.
+    fn change_setting(&mut self, forward: bool) {
+        let Some(menu) = self.pause.as_ref() else {
+            return;
+        };
+        if menu.page != Menu::Settings {
+            return;
+        }
+        let (tick_fps, frame_fps) = (self.settings.tick_fps(), self.settings.frame_fps());
+        let preferences = &mut self.settings.preferences;
+        match SettingsRow::all()[menu.selected] {
+            SettingsRow::Theme => preferences.theme = preferences.theme.cycle(forward),
+            SettingsRow::AnimationSpeed => {
+                preferences.cycle_speed(forward);
+                self.animator.speed = preferences.animation_speed;
+            },
+            //picking a rate in the menu takes over from the command line
+            SettingsRow::TickRate => {
+                preferences.tick_rate = tick_fps;
+                preferences.cycle_tick_rate(forward);
+                self.settings.tick_rate = None;
+                self.apply_rates();
+            },
+            SettingsRow::FrameRate => {
+                preferences.frame_rate = frame_fps;
+                preferences.cycle_frame_rate(forward);
+                self.settings.frame_rate = None;
+                self.apply_rates();
+            },
+            _ => return,
+        }
+        self.save_preferences();
+    }
+
+    /// Hands the tick and frame rates in use to the event task, and the tick rate it settled on to
+    /// everything that counts time in ticks. Rates from the command line are never saved.
+    fn apply_rates(&mut self) {
+        self.events.set_tick_rate(self.settings.tick_fps());
+        self.events.set_frame_rate(self.settings.frame_fps());
+        let tick_fps = self.events.rates().tick_fps;
+        self.turn_timer.set_tick_rate(tick_fps);
+        self.animator.tick_fps = tick_fps;
+    }
+
+    fn save_preferences(&mut self) {
+        if let Some(path) = settings::preferences_path()
+            && let Err(err) = settings::save_preferences(&path, &self.settings.preferences)
+            && let Some(menu) = self.pause.as_mut()
+        {
+            menu.notice = Some(format!("Could not save settings: {err}"));
+        }
+    }
+
+    /// Writes the match to the save slot, asks first if that would replace another save.
+    fn save(&mut self, overwrite: bool) {
+        let Some(path) = save::save_path() else {
+            return;
+        };
+        if path.exists() && !overwrite {
+            self.events.send(AppEvent::Ask(ConfirmAction::OverwriteSave));
+            return;
+        }
+        let save = SaveGame {
+            data: self.data.clone(),
+            match_data: self.match_data.clone(),
+            log: self.log.iter().cloned().collect(),
+        };
+        let notice = match save::save_game(&path, &save) {
+            Ok(()) => String::from("Game saved."),
+            Err(err) => format!("Could not save: {err}"),
+        };
+        if let Some(menu) = self.pause.as_mut() {
+            menu.notice = Some(notice);
+        }
+    }
+
+    /// Who a shot from `shooter` at an opponent hits: the picked target while it is still standing,
+    /// the next player alive otherwise.
+    pub fn target_of(&self, shooter: usize) -> Option<usize> {
+        self.target
+            .filter(|idx| *idx != shooter && self.data.players.get(*idx).is_some_and(Player::is_alive))
+            .or_else(|| self.data.next_alive(shooter))
+    }
+
+    /// Aims at another player, the shooter and the dead can't be picked.
+    fn select_target(&mut self, idx: usize) {
+        let Some(shooter) = self.match_data.turn() else {
+            return;
+        };
+        if idx != shooter && self.data.players.get(idx).is_some_and(Player::is_alive) {
+            self.target = Some(idx);
+        }
+    }
+
+    /// Moves the aim on to the next opponent alive, skipping the shooter.
+    fn next_target(&mut self) {
+        let Some(shooter) = self.match_data.turn() else {
+            return;
+        };
+        let from = self.target_of(shooter).unwrap_or(shooter);
+        let next = self.data.next_alive(from).filter(|next| *next != shooter).or_else(|| self.data.next_alive(shooter));
+        if let Some(next) = next {
+            self.select_target(next);
+        }
+    }
+
+    /// Fires the next shell, at the opponent or at yourself.
+    fn shoot(&mut self, at_self: bool) {
+        if self.turn_timer.in_grace() {
+            self.send_log(Some(format!("Hold your fire, {}s left.", self.turn_timer.grace_seconds())));
+            return;
+        }
+        let Some(shell) = self.data.shotgun.shoot() else {
+            self.send_log(Some("No shell in shotgun.".to_string()));
+            return;
+        };
+        let animation = effects::shot_animation(&shell, self.pack(), self.settings.reduce_motion);
+        self.animator.play(&self.widget_data, animation);
+
+        let shooter = self.match_data.turn();
+        let target = if at_self { shooter } else { shooter.and_then(|idx| self.target_of(idx)) };
+        let what = match (shooter, target) {
+            (Some(shooter), Some(target)) => {
+                let what = format!("{} shot {}", self.data.players[shooter].name, self.data.players[target].name);
+                let health = |players: &[Player]| players.iter().map(|player| player.health as u32).sum::<u32>();
+                let before = health(&self.data.players);
+                self.data.hit(&shell, shooter, target);
+                if health(&self.data.players) < before {
+                    self.animator.play(&self.widget_data, effects::damage_flash(self.settings.reduce_motion));
+                }
+                //a blank at yourself keeps the turn
+                if !(at_self && shell == Shell::Blank) {
+                    self.match_data.pass_turn(&mut self.data.players);
+                    self.target = None;
+                }
+                what
+            },
+            _ => String::from("Popped shell"),
+        };
+
+        let msg = match self.pack().flavour(shell.key()) {
+            Some(flavour) => format!("{}: {:?} - {}", what, shell, flavour),
+            None => format!("{}: {:?}", what, shell),
+        };
+        self.send_log(Some(msg));
+        if self.check_winner() {
+            return;
+        }
+        self.turn_timer.start_turn();
+        self.end_round_if_empty();
+    }
+
+    /// Ends the match once one player is left standing, returns whether it did.
+    ///
+    /// In an endless mode a human winner is asked to go double or nothing, a computer winner
+    /// takes the pot with it.
+    fn check_winner(&mut self) -> bool {
+        let alive: Vec<&Player> = self.data.players.iter().filter(|player| player.is_alive()).collect();
+        if alive.len() > 1 {
+            return false;
+        }
+        let winner = alive.first().map(|player| (player.name.clone(), player.ai));
+        self.data.shotgun.unload();
+        self.turn_timer.stop();
+        self.match_data.set_phase(Phase::Over);
+        let msg = match (self.data.rules.rounds, winner) {
+            (Rounds::Match, Some((name, _))) => format!("{name} wins the match. Load the shotgun for a rematch."),
+            (Rounds::Match, None) => String::from("Nobody is left standing. Load the shotgun for a rematch."),
+            (Rounds::Endless, Some((name, false))) => {
+                let prize = self.match_data.win_stage();
+                self.events.send(AppEvent::Ask(ConfirmAction::DoubleOrNothing));
+                format!("{name} clears the stage for {prize}, the pot is {}.", self.match_data.pot())
+            },
+            (Rounds::Endless, _) => {
+                format!("The pot of {} is lost. Load the shotgun to start over.", self.match_data.take_pot())
+            },
+        };
+        self.send_log(Some(msg));
+        true
+    }
+
+    /// Double or nothing: the table is set again and the next stage pays twice as much.
+    fn next_stage(&mut self) {
+        self.data.shotgun.unload();
+        self.data.players = self.data.rules.players(&self.data.setup.seats);
+        self.match_data.next_stage();
+        self.target = None;
+        let prize = STAGE_PRIZE.saturating_mul(self.match_data.multiplier());
+        self.send_log(Some(format!("Double or nothing, the next stage pays {prize}.")));
+    }
+
+    /// Double or nothing: walks away with what is in the pot.
+    fn cash_out(&mut self) {
+        let pot = self.match_data.take_pot();
+        self.send_log(Some(format!("You walk away with {pot}. Load the shotgun to start over.")));
+    }
+
+    /// The active player took too long, the clock decides for them.
+    fn turn_timeout(&mut self) {
+        let Some(player) = self.active_player() else {
+            return;
+        };
+        let name = player.name.clone();
+        match self.settings.on_timeout {
+            TimeoutAction::PassTurn => {
+                self.send_log(Some(format!("{name} ran out of time.")));
+                self.match_data.pass_turn(&mut self.data.players);
+                self.target = None;
+                self.turn_timer.start_turn();
+            },
+            TimeoutAction::RandomShot => {
+                self.send_log(Some(format!("{name} ran out of time, the shotgun goes off.")));
+                //the match's own dice, so a seeded match plays out the same
+                let at_self = self.rng.gen_bool(0.5);
+                self.shoot(at_self);
+            },
+        }
+    }
+
+    fn cheat(&mut self, cheat: Cheat) {
+        let shotgun = &self.data.shotgun;
+        let msg = match cheat {
+            Cheat::CycleShell(position, forward) => {
+                let Some(shell) = shotgun.peek(position) else {
+                    return;
+                };
+                let next = inspector::next_shell(&shell, forward);
+                shotgun.set(position, next.clone());
+                format!("[cheat] shell #{position}: {shell:?} -> {next:?}")
+            },
+            Cheat::MoveShell(position, later) => {
+                let other = if later { position + 1 } else { position.wrapping_sub(1) };
+                if shotgun.peek(other).is_none() {
+                    return;
+                }
+                shotgun.swap(position, other);
+                format!("[cheat] swapped shells #{position} and #{other}")
+            },
+            Cheat::Health(idx, up) => {
+                let Some(player) = self.data.players.get_mut(idx) else {
+                    return;
+                };
+                if up {
+                    player.heal(1);
+                } else {
+                    player.damage(1);
+                }
+                format!("[cheat] {} health: {}", player.name, player.health)
+            },
+        };
+        self.send_log(Some(msg));
+    }
+
+    /// Uses the item in an inventory slot of the player whose turn it is.
+    fn use_item(&mut self, slot: usize) {
+        let Some(turn) = self.match_data.turn() else {
+            return;
+        };
+        let player = &self.data.players[turn];
+        let Some((item, _)) = player.slots().get(slot).copied() else {
+            return;
+        };
+        if !item.usable(self.match_data.phase(), player, &self.data.shotgun) {
+            self.send_log(Some(format!("{} can't be used right now.", item.name())));
+            return;
+        }
+        let position = self.data.shotgun.position();
+        let msg = self.data.use_item(&mut self.rng, turn, item);
+        self.send_log(msg);
+        //the computer keeps what it saw to itself
+        if item == Items::MagnifyingGlass
+            && let Some(shell) = self.viewer().and_then(|player| player.knows(position))
+        {
+            let animation = effects::reveal_animation(shell, self.pack(), self.settings.reduce_motion);
+            self.animator.play(&self.widget_data, animation);
+        }
+        self.end_round_if_empty();
+    }
+
+    fn end_round_if_empty(&mut self) {
+        if self.data.shotgun.shell_count() == 0 && self.match_data.phase() == Phase::Turn {
+            self.match_data.incr_round();
+            self.match_data.set_phase(Phase::Loading);
+            self.turn_timer.stop();
+        }
+    }
+
+    /// Colors of the theme picked in the settings.
+    pub fn palette(&self) -> Palette {
+        self.settings.preferences.theme.palette()
+    }
+
+    /// The player whose turn it is.
+    pub fn active_player(&self) -> Option<&Player> {
+        self.match_data.turn().and_then(|turn| self.data.players.get(turn))
+    }
+
+    /// The player whose knowledge of the shells the screen shows: whoever's turn it is, unless
+    /// the computer plays it.
+    pub fn viewer(&self) -> Option<&Player> {
+        self.active_player().filter(|player| !player.ai)
+    }
+
+    /// A match counts as started once shells are loaded.
+    pub fn in_match(&self) -> bool {
+        self.data.shotgun.shell_count() > 0 || self.match_data.count() > 1
+    }
+
     fn render_ui(&mut self, frame: &mut Frame){
+        self.screen = ScreenSize::of_area(frame.area());
         let log: Option<String> = ui::render_ui(self, frame);
         self.send_log(log);
     }
 
+    /// The terminal changed size, the next draw lays everything out again.
+    pub fn resize(&mut self, width: u16, height: u16) {
+        self.screen = ScreenSize::of(width, height);
+        //the grab point of a drag means nothing in the new layout
+        self.drag = None;
+    }
+
+    pub fn set_focused(&mut self, focused: bool) {
+        self.focused = focused;
+        self.sync_ticks();
+    }
+
+    /// Game time stands still while the terminal is in the background, the game is paused or
+    /// the title screen is up.
+    fn sync_ticks(&mut self) {
+        self.events.pause_ticks(!self.focused || self.pause.is_some() || self.title.is_some());
+    }
+
     /// Handles the tick event of the terminal.
     ///
     /// The tick event is where you can update the state of your application with any logic that
     /// needs to be updated at a fixed frame rate. E.g. polling a server, updating an animation.
-    pub fn tick(&self) {}
+    pub fn tick(&mut self) {
+        if self.animator.tick(&self.widget_data) {
+            self.redraw.request();
+        }
+
+        //the clock only shows whole seconds, so that is when it needs drawing
+        let shown = (self.turn_timer.grace_seconds(), self.turn_timer.seconds_left());
+        //a question being asked holds the clock, so the turn can't run out under it
+        if self.widget_data.modal().is_none() && self.turn_timer.tick() {
+            self.events.send(AppEvent::TurnTimeout);
+        }
+        if shown != (self.turn_timer.grace_seconds(), self.turn_timer.seconds_left()) {
+            self.redraw.request();
+        }
+        self.play_ai();
+    }
+
+    /// Whether the player who has to act next is played by the computer.
+    pub fn ai_playing(&self) -> bool {
+        let idx = self.match_data.turn().unwrap_or(0);
+        self.data.players.get(idx).is_some_and(|player| player.ai)
+    }
+
+    /// Lets a computer player make its move once it had a moment to think.
+    fn play_ai(&mut self) {
+        //it waits out the grace window and any question that is being asked
+        if !self.ai_playing() || self.turn_timer.in_grace() || self.widget_data.modal().is_some() {
+            self.ai_wait = 0;
+            return;
+        }
+        self.ai_wait += 1;
+        if self.ai_wait < animation::ticks_for(AI_DELAY, self.animator.tick_fps) {
+            return;
+        }
+        self.ai_wait = 0;
+        let idx = self.match_data.turn().unwrap_or(0);
+        match ai::decide(&self.data, self.match_data.phase(), idx) {
+            Some(Move::Load) => self.reload(ReloadAmount::for_round(self.match_data.count())),
+            Some(Move::Use(slot)) => self.use_item(slot),
+            Some(Move::Shoot(at_self)) => self.shoot(at_self),
+            None => {},
+        }
+    }
 
     /// Set running to false to quit the application.
     pub fn quit(&mut self) {
@@ -0,0 +1,215 @@
+//assets.rs
+use std::collections::HashMap;
+use std::path::Path;
+
+use color_eyre::eyre::WrapErr;
+use rand::seq::SliceRandom;
+use serde::Deserialize;
+
+use crate::components::items::Items;
+use crate::components::shotgun::{Shell, ShotgunModel};
+use crate::ui::{BANG, CLICK, SHOTGUN_ART, SHOTGUN_SVG};
+use crate::uihelp::svg::SvgArt;
+
+/// The revolver pack compiled into the binary, see `assets/packs/revolver`.
+const REVOLVER_PACK: EmbeddedPack = EmbeddedPack {
+    manifest: include_str!("../assets/packs/revolver/pack.toml"),
+    files: &[
+        ("revolver.txt", include_str!("../assets/packs/revolver/revolver.txt")),
+        ("bang.txt", include_str!("../assets/packs/revolver/bang.txt")),
+        ("click.txt", include_str!("../assets/packs/revolver/click.txt")),
+    ],
+};
+
+/// A pack bundled into the binary, files are looked up by their name in the manifest.
+struct EmbeddedPack {
+    manifest: &'static str,
+    files: &'static [(&'static str, &'static str)],
+}
+
+/// `pack.toml`, every field is optional so a pack only has to ship what it changes.
+#[derive(Debug, Default, Deserialize)]
+#[serde(default)]
+struct Manifest {
+    name: Option<String>,
+    model: Option<ShotgunModel>,
+    art: ArtFiles,
+    shells: HashMap<Shell, String>,
+    items: HashMap<Items, String>,
+    flavour: HashMap<String, Vec<String>>,
+}
+
+/// Paths of the art files, relative to the pack.
+#[derive(Debug, Default, Deserialize)]
+#[serde(default)]
+struct ArtFiles {
+    shotgun: Option<String>,
+    shotgun_svg: Option<String>,
+    bang: Option<String>,
+    click: Option<String>,
+}
+
+/// Art, icons and flavour text for one look of the game.
+#[derive(Debug, Clone)]
+pub struct AssetPack {
+    pub name: String,
+    pub shotgun: String,
+    pub shotgun_svg: Option<SvgArt>,
+    pub bang: String,
+    pub click: String,
+    shell_icons: HashMap<Shell, String>,
+    item_icons: HashMap<Items, String>,
+    flavour: HashMap<String, Vec<String>>,
+}
+
+impl AssetPack {
+    /// The art compiled into `ui.rs`, every other pack falls back to it.
+    pub fn builtin() -> AssetPack {
+        AssetPack {
+            name: String::from("Default"),
+            shotgun: SHOTGUN_ART.to_string(),
+            shotgun_svg: SvgArt::parse(SHOTGUN_SVG).ok(),
+            bang: BANG.to_string(),
+            click: CLICK.to_string(),
+            shell_icons: HashMap::new(),
+            item_icons: HashMap::new(),
+            flavour: HashMap::new(),
+        }
+    }
+
+    /// Icon for a shell, a single cell wide so it fits in the chamber strip.
+    pub fn shell_icon(&self, shell: &Shell) -> &str {
+        if let Some(icon) = self.shell_icons.get(shell) {
+            return icon;
+        }
+        match shell {
+            Shell::Live => "●",
+            Shell::Blank => "○",
+            Shell::Poison => "☠",
+            Shell::BeanBag => "◍",
+            Shell::Taser => "ϟ",
+            Shell::Imposter => "◌",
+            Shell::SelfDestruct => "✸",
+        }
+    }
+
+    pub fn item_icon(&self, item: &Items) -> &str {
+        if let Some(icon) = self.item_icons.get(item) {
+            return icon;
+        }
+        match item {
+            Items::Saw => "🪚",
+            Items::Beer => "🍺",
+            Items::Cigarette => "🚬",
+            Items::Mirror => "🪞",
+            Items::Inverter => "🔄",
+            Items::MagnifyingGlass => "🔍",
+            Items::Handcuffs => "🔗",
+            Items::Meth => "💊",
+            Items::AED => "💓",
+            Items::Adrenaline => "💉",
+            Items::LSD => "🌈",
+        }
+    }
+
+    /// A random line of flavour text for `key`, if the pack has any.
+    pub fn flavour(&self, key: &str) -> Option<&str> {
+        self.flavour
+            .get(key)
+            .and_then(|lines| lines.choose(&mut rand::thread_rng()))
+            .map(String::as_str)
+    }
+
+    /// Loads a pack from a directory containing a `pack.toml`.
+    pub fn load_dir(dir: &Path) -> color_eyre::Result<(AssetPack, Option<ShotgunModel>)> {
+        let manifest = std::fs::read_to_string(dir.join("pack.toml"))
+            .wrap_err_with(|| format!("no pack.toml in {}", dir.display()))?;
+        Self::from_manifest(&manifest, |file| std::fs::read_to_string(dir.join(file)).ok())
+    }
+
+    fn load_embedded(pack: &EmbeddedPack) -> color_eyre::Result<(AssetPack, Option<ShotgunModel>)> {
+        Self::from_manifest(pack.manifest, |file| {
+            pack.files
+                .iter()
+                .find(|(name, _)| *name == file)
+                .map(|(_, content)| content.to_string())
+        })
+    }
+
+    /// Builds a pack on top of [`AssetPack::builtin`], `read` returns the content of a pack file.
+    fn from_manifest(
+        manifest: &str,
+        read: impl Fn(&str) -> Option<String>,
+    ) -> color_eyre::Result<(AssetPack, Option<ShotgunModel>)> {
+        let manifest: Manifest = toml::from_str(manifest).wrap_err("invalid pack.toml")?;
+        let mut pack = AssetPack::builtin();
+        let art = &manifest.art;
+
+        //missing or unreadable files keep the built-in art
+        let load = |file: &Option<String>, fallback: &mut String| {
+            if let Some(content) = file.as_deref().and_then(&read) {
+                *fallback = content;
+            }
+        };
+        load(&art.shotgun, &mut pack.shotgun);
+        load(&art.bang, &mut pack.bang);
+        load(&art.click, &mut pack.click);
+        if let Some(svg) = art.shotgun_svg.as_deref().and_then(&read) {
+            pack.shotgun_svg = SvgArt::parse(&svg).ok().or(pack.shotgun_svg);
+        }
+
+        if let Some(name) = manifest.name {
+            pack.name = name;
+        }
+        pack.shell_icons = manifest.shells;
+        pack.item_icons = manifest.items;
+        pack.flavour = manifest.flavour;
+        Ok((pack, manifest.model))
+    }
+}
+
+/// Every loaded [`AssetPack`], picked by the model of the shotgun in play.
+#[derive(Debug, Clone)]
+pub struct Assets {
+    default: AssetPack,
+    models: Vec<(ShotgunModel, AssetPack)>,
+}
+
+impl Default for Assets {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Assets {
+    /// The built-in art plus the packs embedded in the binary.
+    pub fn new() -> Assets {
+        let mut assets = Assets { default: AssetPack::builtin(), models: Vec::new() };
+        if let Ok((pack, Some(model))) = AssetPack::load_embedded(&REVOLVER_PACK) {
+            assets.models.push((model, pack));
+        }
+        assets
+    }
+
+    /// Adds a pack from disk. Packs with a `model` replace that model's pack, the rest replace
+    /// the default one.
+    pub fn load_pack(&mut self, dir: &Path) -> color_eyre::Result<()> {
+        let (pack, model) = AssetPack::load_dir(dir)?;
+        match model {
+            Some(model) => {
+                self.models.retain(|(m, _)| *m != model);
+                self.models.push((model, pack));
+            },
+            None => self.default = pack,
+        }
+        Ok(())
+    }
+
+    pub fn pack(&self, model: &ShotgunModel) -> &AssetPack {
+        self.models
+            .iter()
+            .find(|(m, _)| m == model)
+            .map(|(_, pack)| pack)
+            .unwrap_or(&self.default)
+    }
+}
@@ -0,0 +1,183 @@
+//catalog.rs
+use std::path::{Path, PathBuf};
+use std::sync::LazyLock;
+
+use color_eyre::eyre::{eyre, WrapErr};
+use serde::{Deserialize, Serialize};
+
+use crate::components::mode::{GameMode, ModeRules, Rounds, CUSTOM};
+use crate::components::pool::ShellPool;
+use crate::components::setup::GameSetup;
+
+/// The data files compiled into the binary, see `assets/data`.
+const BUILTIN_POOLS: &str = include_str!("../assets/data/pools.toml");
+const BUILTIN_MODES: &str = include_str!("../assets/data/modes.toml");
+
+static BUILTIN: LazyLock<Catalog> = LazyLock::new(|| {
+    let mut catalog = Catalog { pools: Vec::new(), modes: Vec::new() };
+    catalog.add_pools(BUILTIN_POOLS).expect("built-in pools.toml is valid");
+    catalog.add_modes(BUILTIN_MODES).expect("built-in modes.toml is valid");
+    catalog
+});
+
+/// `pools.toml`, a list of `[[pool]]` tables.
+#[derive(Debug, Default, Serialize, Deserialize)]
+struct PoolFile {
+    #[serde(default, rename = "pool")]
+    pools: Vec<ShellPool>,
+}
+
+/// `modes.toml`, a list of `[[mode]]` tables.
+#[derive(Debug, Default, Deserialize)]
+struct ModeFile {
+    #[serde(default, rename = "mode")]
+    modes: Vec<GameMode>,
+}
+
+/// Every shell pool and game mode the game knows about.
+#[derive(Debug, Clone)]
+pub struct Catalog {
+    pub pools: Vec<ShellPool>,
+    pub modes: Vec<GameMode>,
+}
+
+impl Default for Catalog {
+    fn default() -> Self {
+        Self::builtin()
+    }
+}
+
+impl Catalog {
+    /// The pools and modes shipped with the game.
+    pub fn builtin() -> Catalog {
+        BUILTIN.clone()
+    }
+
+    /// Adds the pools and modes from the config directory on top of the built-in ones, a broken
+    /// file is left out and the first error returned.
+    pub fn load_dir(&mut self, dir: &Path) -> color_eyre::Result<()> {
+        //modes can use the pools, so those go first
+        let pools = Self::read(&dir.join("pools.toml"))
+            .and_then(|content| content.map_or(Ok(()), |content| self.add_pools(&content)))
+            .wrap_err("invalid pools.toml");
+        let modes = Self::read(&dir.join("modes.toml"))
+            .and_then(|content| content.map_or(Ok(()), |content| self.add_modes(&content)))
+            .wrap_err("invalid modes.toml");
+        pools.and(modes)
+    }
+
+    /// The content of a data file, a missing file is `None`.
+    fn read(path: &Path) -> color_eyre::Result<Option<String>> {
+        if !path.exists() {
+            return Ok(None);
+        }
+        std::fs::read_to_string(path).map(Some).wrap_err("could not read the file")
+    }
+
+    /// Checks every pool in the file before adding any, pools with a known id replace it.
+    fn add_pools(&mut self, content: &str) -> color_eyre::Result<()> {
+        let file: PoolFile = toml::from_str(content)?;
+        for pool in &file.pools {
+            pool.validate().map_err(|err| eyre!(err))?;
+        }
+        for pool in file.pools {
+            self.set_pool(pool);
+        }
+        Ok(())
+    }
+
+    /// Checks every mode in the file before adding any, modes with a known id replace it.
+    fn add_modes(&mut self, content: &str) -> color_eyre::Result<()> {
+        let file: ModeFile = toml::from_str(content)?;
+        for mode in &file.modes {
+            mode.validate().map_err(|err| eyre!(err))?;
+            if self.pool(&mode.pool).is_none() {
+                return Err(eyre!("mode '{}' uses the unknown pool '{}'", mode.id, mode.pool));
+            }
+        }
+        for mode in file.modes {
+            match self.modes.iter_mut().find(|m| m.id == mode.id) {
+                Some(known) => *known = mode,
+                None => self.modes.push(mode),
+            }
+        }
+        Ok(())
+    }
+
+    pub fn pool(&self, id: &str) -> Option<&ShellPool> {
+        self.pools.iter().find(|pool| pool.id == id)
+    }
+
+    pub fn mode(&self, id: &str) -> Option<&GameMode> {
+        self.modes.iter().find(|mode| mode.id == id)
+    }
+
+    /// Adds a pool, or replaces the one with the same id.
+    pub fn set_pool(&mut self, pool: ShellPool) {
+        match self.pools.iter_mut().find(|p| p.id == pool.id) {
+            Some(known) => *known = pool,
+            None => self.pools.push(pool),
+        }
+    }
+
+    /// Ids of the modes in the wizard, custom comes last.
+    pub fn mode_ids(&self) -> Vec<&str> {
+        self.modes.iter().map(|mode| mode.id.as_str()).chain([CUSTOM]).collect()
+    }
+
+    /// Name of a mode for the wizard and the log.
+    pub fn mode_name(&self, id: &str) -> &str {
+        self.mode(id).map_or("Custom", |mode| mode.name.as_str())
+    }
+
+    /// The rules of the picked mode, or of what was picked for a custom game.
+    pub fn rules(&self, setup: &GameSetup) -> ModeRules {
+        if let Some(mode) = self.mode(&setup.mode)
+            && let Some(pool) = self.pool(&mode.pool)
+        {
+            return ModeRules::new(mode, pool.clone());
+        }
+        let custom = GameMode {
+            id: CUSTOM.to_string(),
+            name: String::from("Custom"),
+            pool: setup.shells().to_string(),
+            items: setup.items(),
+            items_per_load: 2,
+            health: setup.health(),
+            max_health: None,
+            rounds: Rounds::Match,
+        };
+        //a pool that went missing from the config falls back to the first one
+        let pool = self.pool(setup.shells()).unwrap_or(&self.pools[0]);
+        ModeRules::new(&custom, pool.clone())
+    }
+}
+
+/// Whether a pool with this id comes with the game, the pool editor leaves those alone.
+pub fn is_builtin_pool(id: &str) -> bool {
+    BUILTIN.pool(id).is_some()
+}
+
+/// `pools.toml` in the config directory, where the pool editor saves.
+pub fn pools_path() -> Option<PathBuf> {
+    crate::settings::config_dir().map(|dir| dir.join("pools.toml"))
+}
+
+/// Writes a pool to the file, replacing the one with the same id and keeping the rest.
+pub fn save_pool(path: &Path, pool: &ShellPool) -> color_eyre::Result<()> {
+    let mut file: PoolFile = if path.exists() {
+        let content = std::fs::read_to_string(path).wrap_err("could not read pools.toml")?;
+        toml::from_str(&content).wrap_err("invalid pools.toml")?
+    } else {
+        PoolFile::default()
+    };
+    match file.pools.iter_mut().find(|p| p.id == pool.id) {
+        Some(known) => *known = pool.clone(),
+        None => file.pools.push(pool.clone()),
+    }
+    let content = toml::to_string(&file)?;
+    if let Some(dir) = path.parent() {
+        std::fs::create_dir_all(dir)?;
+    }
+    std::fs::write(path, content).wrap_err("could not save pools.toml")
+}
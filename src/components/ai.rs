@@ -0,0 +1,68 @@
+//ai.rs
+use crate::components::enums::Phase;
+use crate::components::items::Items;
+use crate::components::shotgun::Shell;
+use crate::data::Data;
+
+/// What a computer player does next.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Move {
+    Load,
+    /// use the item in an inventory slot
+    Use(usize),
+    /// true to shoot itself
+    Shoot(bool),
+}
+
+/// Picks a move for the computer player at `idx`, `None` when it has nothing to do.
+///
+/// It only goes by what a player at the table could know: the tally of the load, the shells
+/// that already left the chamber and what its own items showed it.
+pub fn decide(data: &Data, phase: Phase, idx: usize) -> Option<Move> {
+    let player = data.players.get(idx)?;
+    if phase == Phase::Over || !player.is_alive() || data.next_alive(idx).is_none() {
+        return None;
+    }
+    if phase == Phase::Loading {
+        return Some(Move::Load);
+    }
+
+    let shotgun = &data.shotgun;
+    let slot = |item: Items| {
+        player
+            .slots()
+            .iter()
+            .position(|(kind, _)| *kind == item)
+            .filter(|_| item.usable(phase, player, shotgun))
+    };
+
+    if player.health < player.max_health
+        && let Some(slot) = slot(Items::Cigarette).or_else(|| slot(Items::Meth))
+    {
+        return Some(Move::Use(slot));
+    }
+    let known = player.knows(shotgun.position());
+    if known.is_none()
+        && let Some(slot) = slot(Items::MagnifyingGlass)
+    {
+        return Some(Move::Use(slot));
+    }
+
+    let live_next = match known {
+        Some(shell) => *shell != Shell::Blank,
+        None => {
+            //imposters look like blanks, so they are counted as blanks
+            let blanks = |shells: &[Shell]| shells.iter().filter(|shell| shell.disguised() == Shell::Blank).count();
+            let blank = blanks(&shotgun.loaded.borrow()).saturating_sub(blanks(&shotgun.fired.borrow()));
+            let live = shotgun.shell_count().saturating_sub(blank);
+            live >= blank
+        },
+    };
+    if !live_next {
+        return Some(Move::Shoot(true));
+    }
+    if let Some(slot) = slot(Items::Saw) {
+        return Some(Move::Use(slot));
+    }
+    Some(Move::Shoot(false))
+}
@@ -1,9 +1,61 @@
-#[derive(Default, Debug)]
+use serde::{Deserialize, Serialize};
+
+/// Which page of the pause menu is showing.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
 pub enum Menu {
     #[default]
-    Off,
-    Log,
-    Help,
+    Pause,
+    Settings,
+    Rules,
+}
+
+/// Which page of the title screen is showing.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum TitlePage {
+    #[default]
+    Main,
+    /// the New Game wizard
+    NewGame,
+    /// building custom shell pools
+    PoolEditor,
+    /// playing a match again from its seed
+    Replays,
+}
+
+/// What part of a round the match is in.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Phase {
+    /// the shotgun is empty and waiting to be loaded
+    #[default]
+    Loading,
+    /// the active player can use items and shoot
+    Turn,
+    /// one player is left standing
+    Over,
+}
+
+/// Something that has to be confirmed before it happens.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ConfirmAction {
+    /// leaving while a match is going
+    QuitToTitle,
+    ShootSelf,
+    Forfeit,
+    /// saving over the game that is already saved
+    OverwriteSave,
+    /// playing another stage for twice the prize, saying no cashes out
+    DoubleOrNothing,
+}
+
+/// What happens when the turn clock runs out.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum TimeoutAction {
+    /// the turn goes to the next player without a shot
+    #[default]
+    PassTurn,
+    /// the shotgun goes off at a random target
+    RandomShot,
 }
 
 #[derive(Debug, Clone, Copy, Default)]
@@ -18,6 +70,17 @@ pub enum ReloadAmount {
 
 impl ReloadAmount {
     pub fn as_usize(&self) -> usize {
-        self.clone() as usize
+        *self as usize
+    }
+
+    /// How many shells go in for a round, later rounds load more.
+    pub fn for_round(round: u8) -> ReloadAmount {
+        match round {
+            0 | 1 => ReloadAmount::One,
+            2 => ReloadAmount::Two,
+            3 => ReloadAmount::Three,
+            4 => ReloadAmount::Four,
+            _ => ReloadAmount::Five,
+        }
     }
 }
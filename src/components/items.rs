@@ -1,9 +1,14 @@
 //items.rs
-use rand::thread_rng;
+use serde::{Deserialize, Serialize};
+
+use crate::components::enums::Phase;
+use crate::components::player::Player;
+use crate::components::shotgun::{Shotgun, ShotgunState};
 
 //local and online multiplayer, and singleplayer
 //multiplayer items easier to pull off, ten second timer is when a shot could be first fired
-#[derive(Clone, Copy, Debug)]
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
 pub enum Items {
     Saw, //doubles damage
     Beer, //ejects the next shell
@@ -13,7 +18,74 @@ pub enum Items {
     MagnifyingGlass, //shows current shell
     Handcuffs, //skips next player's turn
     Meth, //restores two health, but keeps you from being able to see the next shell
+    #[serde(rename = "aed")]
     AED, //secret and default, if shot next round it keeps the health, if not lose a health
     Adrenaline,
+    #[serde(rename = "lsd")]
     LSD, //shows a shell in the future, specifies which one
 }
+
+impl Items {
+    pub const ALL: [Items; 11] = [
+        Items::Saw,
+        Items::Beer,
+        Items::Cigarette,
+        Items::Mirror,
+        Items::Inverter,
+        Items::MagnifyingGlass,
+        Items::Handcuffs,
+        Items::Meth,
+        Items::AED,
+        Items::Adrenaline,
+        Items::LSD,
+    ];
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            Items::Saw => "Saw",
+            Items::Beer => "Beer",
+            Items::Cigarette => "Cigarette",
+            Items::Mirror => "Mirror",
+            Items::Inverter => "Inverter",
+            Items::MagnifyingGlass => "Magnifying Glass",
+            Items::Handcuffs => "Handcuffs",
+            Items::Meth => "Meth",
+            Items::AED => "AED",
+            Items::Adrenaline => "Adrenaline",
+            Items::LSD => "LSD",
+        }
+    }
+
+    pub fn description(&self) -> &'static str {
+        match self {
+            Items::Saw => "Saws off the barrel, the next shot does double damage.",
+            Items::Beer => "Racks the shotgun and ejects the next shell.",
+            Items::Cigarette => "Restores one health.",
+            Items::Mirror => "Deflects the next shot back at the shooter.",
+            Items::Inverter => "Swaps the next shell between live and blank.",
+            Items::MagnifyingGlass => "Shows the shell in the chamber.",
+            Items::Handcuffs => "The next player skips their turn.",
+            Items::Meth => "Restores two health, but you can't see the next shell.",
+            Items::AED => "Works on its own: survive the next round and keep your health.",
+            Items::Adrenaline => "Steal an item from another player and use it.",
+            Items::LSD => "Shows a shell further down the chamber.",
+        }
+    }
+
+    /// Whether `player` can use the item right now.
+    pub fn usable(&self, phase: Phase, player: &Player, shotgun: &Shotgun) -> bool {
+        if phase != Phase::Turn {
+            return false;
+        }
+        match self {
+            Items::Saw => !matches!(shotgun.state, ShotgunState::SawedOff | ShotgunState::ThickBarrel),
+            Items::Cigarette | Items::Meth => player.health < player.max_health,
+            Items::LSD => shotgun.shell_count() > 1,
+            //the AED goes off by itself
+            Items::AED => false,
+            //not in the game yet
+            Items::Mirror | Items::Adrenaline => false,
+            _ => shotgun.shell_count() > 0,
+        }
+    }
+}
@@ -1,9 +1,25 @@
-#[derive(Debug, Default, Clone)]
+use serde::{Deserialize, Serialize};
+
+use crate::components::enums::Phase;
+use crate::components::mode::STAGE_PRIZE;
+use crate::components::player::{Player, Status};
+
+#[derive(Debug, Default, Clone, Serialize, Deserialize)]
 pub struct MatchData {
     //round count
     count: u8,
-    //represents the players
-    turn: Option<u16>,
+    //index of the player whose turn it is
+    turn: Option<usize>,
+    phase: Phase,
+    //double or nothing: what has been won so far and what the next stage is worth
+    #[serde(default)]
+    pot: u32,
+    #[serde(default = "first_multiplier")]
+    multiplier: u32,
+}
+
+fn first_multiplier() -> u32 {
+    1
 }
 
 impl MatchData {
@@ -11,9 +27,41 @@ impl MatchData {
         MatchData {
             count: 1,
             turn: None,
+            phase: Phase::Loading,
+            pot: 0,
+            multiplier: first_multiplier(),
         }
     }
 
+    pub fn pot(&self) -> u32 {
+        self.pot
+    }
+
+    pub fn multiplier(&self) -> u32 {
+        self.multiplier
+    }
+
+    /// Adds the prize of the stage to the pot, returns the prize.
+    pub fn win_stage(&mut self) -> u32 {
+        let prize = STAGE_PRIZE.saturating_mul(self.multiplier);
+        self.pot = self.pot.saturating_add(prize);
+        prize
+    }
+
+    /// Sets up the next stage for twice the prize, the pot stays.
+    pub fn next_stage(&mut self) {
+        *self = MatchData {
+            pot: self.pot,
+            multiplier: self.multiplier.saturating_mul(2),
+            ..MatchData::new()
+        };
+    }
+
+    /// Empties the pot, returns what was in it.
+    pub fn take_pot(&mut self) -> u32 {
+        std::mem::take(&mut self.pot)
+    }
+
     pub fn incr_round(&mut self) {
         self.count += 1;
     }
@@ -22,7 +70,102 @@ impl MatchData {
         self.count
     }
 
-    pub fn reset_count(&mut self) {
-        self.count = 0;
+    /// The player whose turn it is, `None` before the first load.
+    pub fn turn(&self) -> Option<usize> {
+        self.turn
+    }
+
+    pub fn set_turn(&mut self, turn: Option<usize>) {
+        self.turn = turn;
+    }
+
+    /// Hands the turn to the next living player, cuffed players are skipped once.
+    pub fn pass_turn(&mut self, players: &mut [Player]) {
+        let Some(current) = self.turn else {
+            return;
+        };
+        let len = players.len();
+        //twice around so a cuffed player can still get the turn if nobody else can
+        for step in 1..=len * 2 {
+            let idx = (current + step) % len;
+            let player = &mut players[idx];
+            if !player.is_alive() {
+                continue;
+            }
+            if player.remove_status(Status::Cuffed) {
+                continue;
+            }
+            self.turn = Some(idx);
+            return;
+        }
+    }
+
+    pub fn phase(&self) -> Phase {
+        self.phase
+    }
+
+    pub fn set_phase(&mut self, phase: Phase) {
+        self.phase = phase;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn table(count: usize) -> (MatchData, Vec<Player>) {
+        let mut match_data = MatchData::new();
+        match_data.set_turn(Some(0));
+        let players = (0..count).map(|idx| Player::new(format!("Player {}", idx + 1), idx as u8)).collect();
+        (match_data, players)
+    }
+
+    #[test]
+    fn the_turn_goes_around_the_table() {
+        let (mut match_data, mut players) = table(3);
+        match_data.pass_turn(&mut players);
+        assert_eq!(match_data.turn(), Some(1));
+        match_data.pass_turn(&mut players);
+        match_data.pass_turn(&mut players);
+        assert_eq!(match_data.turn(), Some(0));
+    }
+
+    #[test]
+    fn dead_players_are_skipped() {
+        let (mut match_data, mut players) = table(3);
+        players[1].health = 0;
+        match_data.pass_turn(&mut players);
+        assert_eq!(match_data.turn(), Some(2));
+    }
+
+    #[test]
+    fn a_cuffed_player_misses_one_turn() {
+        let (mut match_data, mut players) = table(3);
+        players[1].add_status(Status::Cuffed);
+        match_data.pass_turn(&mut players);
+        assert_eq!(match_data.turn(), Some(2));
+        assert!(!players[1].has(Status::Cuffed));
+
+        match_data.pass_turn(&mut players);
+        match_data.pass_turn(&mut players);
+        assert_eq!(match_data.turn(), Some(1));
+    }
+
+    #[test]
+    fn cuffing_the_only_opponent_hands_the_turn_back() {
+        let (mut match_data, mut players) = table(2);
+        players[1].add_status(Status::Cuffed);
+        match_data.pass_turn(&mut players);
+        assert_eq!(match_data.turn(), Some(0));
+        match_data.pass_turn(&mut players);
+        assert_eq!(match_data.turn(), Some(1));
+    }
+
+    #[test]
+    fn nothing_happens_before_the_first_load() {
+        let (mut match_data, mut players) = table(2);
+        match_data.set_turn(None);
+        match_data.pass_turn(&mut players);
+        assert_eq!(match_data.turn(), None);
     }
 }
@@ -3,3 +3,8 @@ pub mod shotgun;
 pub mod player;
 pub mod enums;
 pub mod match_data;
+pub mod turn_timer;
+pub mod setup;
+pub mod ai;
+pub mod mode;
+pub mod pool;
@@ -0,0 +1,158 @@
+//mode.rs
+use serde::{Deserialize, Serialize};
+
+use crate::components::items::Items;
+use crate::components::player::Player;
+use crate::components::pool::ShellPool;
+use crate::catalog::Catalog;
+use crate::components::setup::{GameSetup, Seat, MAX_START_HEALTH};
+
+/// What a won stage of Double or Nothing adds to the pot, before the multiplier.
+pub const STAGE_PRIZE: u32 = 100;
+/// The mode where shells, items and health are picked by hand in the wizard.
+pub const CUSTOM: &str = "custom";
+
+/// Items handed out in a classic match.
+const BASIC_ITEMS: [Items; 5] = [
+    Items::Saw,
+    Items::Beer,
+    Items::Cigarette,
+    Items::MagnifyingGlass,
+    Items::Handcuffs,
+];
+/// Every item that does something so far, Mirror, AED and Adrenaline are left out until they do.
+const ALL_ITEMS: [Items; 8] = [
+    Items::Saw,
+    Items::Beer,
+    Items::Cigarette,
+    Items::Inverter,
+    Items::MagnifyingGlass,
+    Items::Handcuffs,
+    Items::Meth,
+    Items::LSD,
+];
+
+/// How a match goes on once one player is left standing.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Rounds {
+    /// the last one standing wins and that's it
+    #[default]
+    Match,
+    /// the table is set again for the next stage, for as long as the winner dares
+    Endless,
+}
+
+/// Which items players can get.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ItemPool {
+    #[default]
+    Basic,
+    All,
+    None,
+}
+
+impl ItemPool {
+    pub const ALL: [ItemPool; 3] = [ItemPool::Basic, ItemPool::All, ItemPool::None];
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            ItemPool::Basic => "Basic",
+            ItemPool::All => "All",
+            ItemPool::None => "None",
+        }
+    }
+
+    pub fn items(&self) -> &'static [Items] {
+        match self {
+            ItemPool::Basic => &BASIC_ITEMS,
+            ItemPool::All => &ALL_ITEMS,
+            ItemPool::None => &[],
+        }
+    }
+}
+
+/// A game mode as written in `modes.toml`, the shell pool is named by its id.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct GameMode {
+    pub id: String,
+    pub name: String,
+    pub pool: String,
+    pub items: ItemPool,
+    /// items every player gets when the shotgun is loaded
+    pub items_per_load: usize,
+    /// health every player starts with
+    pub health: u8,
+    /// what healing tops out at, the starting health when not set
+    #[serde(default)]
+    pub max_health: Option<u8>,
+    #[serde(default)]
+    pub rounds: Rounds,
+}
+
+impl GameMode {
+    /// Checks the numbers, the pool is checked when the mode is added to the catalog.
+    pub fn validate(&self) -> Result<(), String> {
+        if self.id.is_empty() || self.id == CUSTOM {
+            return Err(format!("mode '{}' needs an id other than '{CUSTOM}'", self.name));
+        }
+        if !(1..=MAX_START_HEALTH).contains(&self.health) {
+            return Err(format!("mode '{}': health has to be between 1 and {MAX_START_HEALTH}", self.id));
+        }
+        if self.max_health.is_some_and(|max| max < self.health) {
+            return Err(format!("mode '{}': max_health is below the starting health", self.id));
+        }
+        Ok(())
+    }
+}
+
+/// Everything a mode decides about a match, with the shell pool looked up.
+///
+/// A match keeps its own copy, so a save plays the same even if the data files change.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ModeRules {
+    pub name: String,
+    pub pool: ShellPool,
+    pub items: ItemPool,
+    pub items_per_load: usize,
+    pub health: u8,
+    pub max_health: u8,
+    pub rounds: Rounds,
+}
+
+impl Default for ModeRules {
+    /// The rules of a new game with nothing picked, for saves from before modes had rules.
+    fn default() -> Self {
+        Catalog::builtin().rules(&GameSetup::new())
+    }
+}
+
+impl ModeRules {
+    pub fn new(mode: &GameMode, pool: ShellPool) -> ModeRules {
+        ModeRules {
+            name: mode.name.clone(),
+            pool,
+            items: mode.items,
+            items_per_load: mode.items_per_load,
+            health: mode.health,
+            max_health: mode.max_health.unwrap_or(mode.health),
+            rounds: mode.rounds,
+        }
+    }
+
+    /// The players a match starts with, one for each seat.
+    pub fn players(&self, seats: &[Seat]) -> Vec<Player> {
+        seats
+            .iter()
+            .enumerate()
+            .map(|(idx, seat)| {
+                let mut player = Player::new(seat.name.clone(), idx as u8);
+                player.ai = seat.ai;
+                player.health = self.health;
+                player.max_health = self.max_health;
+                player
+            })
+            .collect()
+    }
+}
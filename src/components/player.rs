@@ -1,9 +1,140 @@
+use rand::Rng;
+use rand::seq::SliceRandom;
+use serde::{Deserialize, Serialize};
+
 use crate::components::items::Items;
+use crate::components::shotgun::Shell;
+
+/// Health every player starts a match with.
+pub const MAX_HEALTH: u8 = 4;
+/// How many items fit in an inventory.
+pub const MAX_ITEMS: usize = 8;
+
+/// Lasting effects on a player, shown on the player board.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Status {
+    Stunned, //bean bag or taser, only one item next turn
+    Poisoned,
+    Cuffed, //skips their next turn
+}
+
+impl Status {
+    pub fn name(&self) -> &'static str {
+        match self {
+            Status::Stunned => "stunned",
+            Status::Poisoned => "poisoned",
+            Status::Cuffed => "cuffed",
+        }
+    }
+}
 
-#[derive(Debug, Clone)]
+fn default_max_health() -> u8 {
+    MAX_HEALTH
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct Player {
-    name: String,
-    id: u8,
-    health: u8,
-    items: Vec<Items>
+    pub name: String,
+    pub id: u8,
+    pub health: u8,
+    /// what healing tops out at
+    #[serde(default = "default_max_health")]
+    pub max_health: u8,
+    /// played by the computer
+    #[serde(default)]
+    pub ai: bool,
+    pub items: Vec<Items>,
+    pub statuses: Vec<Status>,
+    //shells this player has seen, by position in the round
+    pub known: Vec<(usize, Shell)>,
+}
+
+impl Player {
+    pub fn new(name: impl Into<String>, id: u8) -> Player {
+        Player {
+            name: name.into(),
+            id,
+            health: MAX_HEALTH,
+            max_health: MAX_HEALTH,
+            ai: false,
+            items: Vec::new(),
+            statuses: Vec::new(),
+            known: Vec::new(),
+        }
+    }
+
+    pub fn is_alive(&self) -> bool {
+        self.health > 0
+    }
+
+    pub fn has(&self, status: Status) -> bool {
+        self.statuses.contains(&status)
+    }
+
+    pub fn add_status(&mut self, status: Status) {
+        if !self.has(status) {
+            self.statuses.push(status);
+        }
+    }
+
+    /// Takes the status off, returns whether the player had it.
+    pub fn remove_status(&mut self, status: Status) -> bool {
+        let had = self.has(status);
+        self.statuses.retain(|s| *s != status);
+        had
+    }
+
+    /// What this player saw at a position in the round, if anything.
+    pub fn knows(&self, position: usize) -> Option<&Shell> {
+        self.known.iter().find(|(p, _)| *p == position).map(|(_, shell)| shell)
+    }
+
+    pub fn reveal(&mut self, position: usize, shell: Shell) {
+        self.known.retain(|(p, _)| *p != position);
+        self.known.push((position, shell));
+    }
+
+    /// Removes one of `item` from the inventory, returns whether there was one.
+    pub fn take_item(&mut self, item: Items) -> bool {
+        match self.items.iter().position(|i| *i == item) {
+            Some(idx) => {
+                self.items.remove(idx);
+                true
+            },
+            None => false,
+        }
+    }
+
+    pub fn heal(&mut self, amount: u8) {
+        self.health = (self.health + amount).min(self.max_health);
+    }
+
+    pub fn damage(&mut self, amount: u8) {
+        self.health = self.health.saturating_sub(amount);
+    }
+
+    /// Items grouped by kind in the order they were picked up, with how many of each there are.
+    pub fn slots(&self) -> Vec<(Items, usize)> {
+        let mut slots: Vec<(Items, usize)> = Vec::new();
+        for item in &self.items {
+            match slots.iter_mut().find(|(kind, _)| kind == item) {
+                Some((_, count)) => *count += 1,
+                None => slots.push((*item, 1)),
+            }
+        }
+        slots
+    }
+
+    /// Hands out random items from `pool`, whatever doesn't fit in the inventory is lost.
+    pub fn give_random_items(&mut self, rng: &mut impl Rng, pool: &[Items], count: usize) {
+        for _ in 0..count {
+            if self.items.len() >= MAX_ITEMS {
+                break;
+            }
+            if let Some(item) = pool.choose(rng) {
+                self.items.push(*item);
+            }
+        }
+    }
 }
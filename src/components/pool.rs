@@ -0,0 +1,202 @@
+//pool.rs
+use std::collections::BTreeMap;
+
+use rand::distributions::{Distribution, WeightedIndex};
+use rand::seq::SliceRandom;
+use rand::Rng;
+use serde::{Deserialize, Serialize};
+
+use crate::components::enums::ReloadAmount;
+use crate::components::shotgun::Shell;
+
+/// How often a kind of shell comes up and how many of it one load can have.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ShellRule {
+    /// chance against the other shells, 0 only loads the minimum
+    pub weight: u32,
+    #[serde(default)]
+    pub min: usize,
+    /// no limit when not set
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub max: Option<usize>,
+}
+
+impl ShellRule {
+    pub fn new(weight: u32) -> ShellRule {
+        ShellRule { weight, min: 0, max: None }
+    }
+}
+
+/// A mix of shells the shotgun is loaded from, defined in `pools.toml`.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ShellPool {
+    pub id: String,
+    pub name: String,
+    /// every load has at least one blank
+    #[serde(default = "guaranteed_blank")]
+    pub guaranteed_blank: bool,
+    pub shells: BTreeMap<Shell, ShellRule>,
+}
+
+fn guaranteed_blank() -> bool {
+    true
+}
+
+impl ShellPool {
+    /// Checks that every load the game can ask for can be filled by the rules.
+    pub fn validate(&self) -> Result<(), String> {
+        let smallest = ReloadAmount::One.as_usize();
+        let largest = ReloadAmount::Five.as_usize();
+        if self.id.is_empty() {
+            return Err(format!("pool '{}' has no id", self.name));
+        }
+        for (shell, rule) in &self.shells {
+            if rule.max.is_some_and(|max| rule.min > max) {
+                return Err(format!("pool '{}': the min of {} is above its max", self.id, shell.key()));
+            }
+        }
+        let blank = self.shells.get(&Shell::Blank);
+        if self.guaranteed_blank && blank.is_some_and(|rule| rule.max == Some(0)) {
+            return Err(format!("pool '{}' guarantees a blank but allows none", self.id));
+        }
+        if self.required() > smallest {
+            return Err(format!("pool '{}' needs more than the {smallest} shells of the smallest load", self.id));
+        }
+        //shells that never get drawn only ever fill their minimum
+        let room = self
+            .shells
+            .values()
+            .map(|rule| if rule.weight > 0 { rule.max.unwrap_or(largest).max(rule.min) } else { rule.min })
+            .sum::<usize>();
+        if room.max(self.required()) < largest {
+            return Err(format!("pool '{}' can't fill a load of {largest} shells", self.id));
+        }
+        Ok(())
+    }
+
+    /// Shells every load starts with: the minimums, and a blank if one is guaranteed.
+    fn required(&self) -> usize {
+        let min = self.shells.values().map(|rule| rule.min).sum::<usize>();
+        let blank_min = self.shells.get(&Shell::Blank).map_or(0, |rule| rule.min);
+        if self.guaranteed_blank && blank_min == 0 { min + 1 } else { min }
+    }
+
+    /// Draws a load of `count` shells in random order.
+    pub fn deal(&self, rng: &mut impl Rng, count: usize) -> Vec<Shell> {
+        let mut shells: Vec<Shell> = Vec::new();
+        for (shell, rule) in &self.shells {
+            shells.extend(std::iter::repeat_n(shell.clone(), rule.min));
+        }
+        if self.guaranteed_blank && !shells.contains(&Shell::Blank) {
+            shells.push(Shell::Blank);
+        }
+        while shells.len() < count {
+            let open: Vec<(&Shell, u32)> = self
+                .shells
+                .iter()
+                .filter(|(shell, rule)| rule.max.is_none_or(|max| shells.iter().filter(|s| s == shell).count() < max))
+                .map(|(shell, rule)| (shell, rule.weight))
+                .collect();
+            //validated pools don't run dry, but a half edited one might
+            let Ok(dist) = WeightedIndex::new(open.iter().map(|(_, weight)| *weight)) else {
+                break;
+            };
+            shells.push(open[dist.sample(rng)].0.clone());
+        }
+        shells.shuffle(rng);
+        shells
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use rand::rngs::StdRng;
+    use rand::SeedableRng;
+
+    use super::*;
+    use crate::catalog::Catalog;
+
+    fn pool(shells: &[(Shell, ShellRule)]) -> ShellPool {
+        ShellPool {
+            id: String::from("test"),
+            name: String::from("Test"),
+            guaranteed_blank: true,
+            shells: shells.iter().cloned().collect(),
+        }
+    }
+
+    fn rule(weight: u32, min: usize, max: Option<usize>) -> ShellRule {
+        ShellRule { weight, min, max }
+    }
+
+    fn count(shells: &[Shell], shell: Shell) -> usize {
+        shells.iter().filter(|s| **s == shell).count()
+    }
+
+    #[test]
+    fn built_in_pools_are_valid() {
+        for pool in Catalog::builtin().pools {
+            assert_eq!(pool.validate(), Ok(()), "{}", pool.id);
+        }
+    }
+
+    #[test]
+    fn rejects_rules_that_contradict_each_other() {
+        let mut broken = pool(&[(Shell::Live, rule(1, 3, Some(2))), (Shell::Blank, rule(1, 0, None))]);
+        assert!(broken.validate().unwrap_err().contains("above its max"));
+
+        broken = pool(&[(Shell::Live, rule(1, 0, None)), (Shell::Blank, rule(1, 0, Some(0)))]);
+        assert!(broken.validate().unwrap_err().contains("allows none"));
+
+        broken.id.clear();
+        assert!(broken.validate().unwrap_err().contains("no id"));
+    }
+
+    #[test]
+    fn rejects_pools_that_dont_fit_every_load() {
+        //three live and the guaranteed blank is more than the smallest load
+        let too_many = pool(&[(Shell::Live, rule(1, 3, None)), (Shell::Blank, rule(1, 0, None))]);
+        assert!(too_many.validate().unwrap_err().contains("smallest load"));
+
+        let too_few = pool(&[(Shell::Live, rule(1, 0, Some(4))), (Shell::Blank, rule(1, 0, Some(4)))]);
+        assert!(too_few.validate().unwrap_err().contains("can't fill"));
+
+        //a shell with no weight only counts its minimum
+        let unweighted = pool(&[(Shell::Live, rule(0, 1, None)), (Shell::Blank, rule(1, 0, Some(5)))]);
+        assert!(unweighted.validate().is_err());
+    }
+
+    #[test]
+    fn deals_the_minimums_and_keeps_to_the_maximums() {
+        let pool = pool(&[
+            (Shell::Live, rule(1, 0, Some(2))),
+            (Shell::Blank, rule(1, 0, None)),
+            (Shell::Taser, rule(0, 1, None)),
+        ]);
+        assert_eq!(pool.validate(), Ok(()));
+        let mut rng = StdRng::seed_from_u64(7);
+        for _ in 0..50 {
+            let shells = pool.deal(&mut rng, ReloadAmount::Five.as_usize());
+            assert_eq!(shells.len(), 10);
+            assert!(count(&shells, Shell::Live) <= 2);
+            assert_eq!(count(&shells, Shell::Taser), 1);
+            assert!(count(&shells, Shell::Blank) >= 1);
+        }
+    }
+
+    #[test]
+    fn a_guaranteed_blank_is_always_dealt() {
+        let pool = pool(&[(Shell::Live, rule(100, 0, None)), (Shell::Blank, rule(0, 0, None))]);
+        let mut rng = StdRng::seed_from_u64(1);
+        for _ in 0..20 {
+            assert_eq!(count(&pool.deal(&mut rng, 3), Shell::Blank), 1);
+        }
+    }
+
+    #[test]
+    fn the_same_seed_deals_the_same_shells() {
+        let pool = Catalog::builtin().pool("random").cloned().unwrap();
+        let deal = |seed| pool.deal(&mut StdRng::seed_from_u64(seed), 8);
+        assert_eq!(deal(42), deal(42));
+    }
+}
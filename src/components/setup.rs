@@ -0,0 +1,107 @@
+//setup.rs
+use serde::{Deserialize, Serialize};
+
+use crate::components::mode::ItemPool;
+use crate::components::player::MAX_HEALTH;
+
+/// Fewest and most seats at the table.
+pub const MIN_PLAYERS: usize = 2;
+pub const MAX_PLAYERS: usize = 4;
+/// Most health a match can start with.
+pub const MAX_START_HEALTH: u8 = 8;
+/// Highest seed the wizard takes, saves are toml and its integers stop at `i64::MAX`.
+pub const MAX_SEED: u64 = i64::MAX as u64;
+
+/// The seed with a digit typed on the end, unchanged when it isn't a digit or goes past [`MAX_SEED`].
+pub fn type_seed(seed: Option<u64>, key: char) -> Option<u64> {
+    key.to_digit(10)
+        .and_then(|digit| seed.unwrap_or(0).checked_mul(10)?.checked_add(digit as u64))
+        .filter(|typed| *typed <= MAX_SEED)
+        .or(seed)
+}
+
+/// The seed without its last digit, none once it is empty.
+pub fn erase_seed(seed: Option<u64>) -> Option<u64> {
+    seed.filter(|seed| *seed >= 10).map(|seed| seed / 10)
+}
+
+/// A seat at the table.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Seat {
+    pub name: String,
+    /// played by the computer
+    pub ai: bool,
+}
+
+impl Seat {
+    fn new(idx: usize) -> Seat {
+        Seat { name: format!("Player {}", idx + 1), ai: false }
+    }
+}
+
+/// Everything picked in the New Game wizard.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct GameSetup {
+    /// id of a mode from the catalog, or `custom`
+    pub mode: String,
+    pub seats: Vec<Seat>,
+    //only used by custom games, the other modes bring their own
+    health: u8,
+    shells: String,
+    items: ItemPool,
+    /// same seed, same shells and items
+    pub seed: Option<u64>,
+}
+
+impl Default for GameSetup {
+    fn default() -> Self {
+        GameSetup {
+            mode: String::from("classic"),
+            seats: vec![Seat::new(0), Seat { name: String::from("Dealer"), ai: true }],
+            health: MAX_HEALTH,
+            shells: String::from("default"),
+            items: ItemPool::default(),
+            seed: None,
+        }
+    }
+}
+
+impl GameSetup {
+    pub fn new() -> GameSetup {
+        GameSetup::default()
+    }
+
+    /// Adds or removes seats from the end, keeping the ones that stay.
+    pub fn set_player_count(&mut self, count: usize) {
+        let count = count.clamp(MIN_PLAYERS, MAX_PLAYERS);
+        self.seats.truncate(count);
+        while self.seats.len() < count {
+            self.seats.push(Seat::new(self.seats.len()));
+        }
+    }
+
+    /// Picks for a custom game, the other modes ignore them.
+    pub fn shells(&self) -> &str {
+        &self.shells
+    }
+
+    pub fn set_shells(&mut self, pool: &str) {
+        self.shells = pool.to_string();
+    }
+
+    pub fn items(&self) -> ItemPool {
+        self.items
+    }
+
+    pub fn set_items(&mut self, items: ItemPool) {
+        self.items = items;
+    }
+
+    pub fn health(&self) -> u8 {
+        self.health
+    }
+
+    pub fn set_health(&mut self, health: u8) {
+        self.health = health.clamp(1, MAX_START_HEALTH);
+    }
+}
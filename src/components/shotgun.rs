@@ -1,22 +1,31 @@
 //shotgun.rs
-use rand::{ Rng, seq::SliceRandom, thread_rng, distributions::{WeightedIndex, Distribution} };
+use rand::Rng;
 use std::cell::RefCell;
+use serde::{Deserialize, Serialize};
 
-#[derive(Debug, Default, Clone)]
+use crate::components::pool::ShellPool;
+
+#[derive(Debug, Default, Clone, Serialize, Deserialize)]
 pub struct Shotgun {
+    //the next shell to fire is the last one
     pub shells: RefCell<Vec<Shell>>,
+    //what was loaded this round and what already left the chamber, in firing order
+    pub loaded: RefCell<Vec<Shell>>,
+    pub fired: RefCell<Vec<Shell>>,
     pub state: ShotgunState,
     pub model: ShotgunModel,
 }
 
-#[derive(Debug, Default, Clone)]
+#[derive(Debug, Default, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
 pub enum ShotgunModel {
     #[default]
     Default,
     Revolver, //does twice the amount of damage
 }
 
-#[derive(Debug, Default, Clone)]
+#[derive(Debug, Default, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
 pub enum ShotgunState {
     #[default]
     Default,
@@ -26,7 +35,8 @@ pub enum ShotgunState {
     Reinforced, //Destruct shell becomes offensive but also destroys the shotgun
 }
 
-#[derive(Debug, Default, Clone, PartialEq)]
+#[derive(Debug, Default, Clone, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
 pub enum Shell {
     Live,
     #[default]
@@ -38,6 +48,39 @@ pub enum Shell {
     SelfDestruct, //blows up in the person's face if not reinforced
 }
 
+impl Shell {
+    pub const ALL: [Shell; 7] = [
+        Shell::Live,
+        Shell::Blank,
+        Shell::Poison,
+        Shell::BeanBag,
+        Shell::Taser,
+        Shell::Imposter,
+        Shell::SelfDestruct,
+    ];
+
+    /// Name used for the shell in asset packs and data files.
+    pub fn key(&self) -> &'static str {
+        match self {
+            Shell::Live => "live",
+            Shell::Blank => "blank",
+            Shell::Poison => "poison",
+            Shell::BeanBag => "bean_bag",
+            Shell::Taser => "taser",
+            Shell::Imposter => "imposter",
+            Shell::SelfDestruct => "self_destruct",
+        }
+    }
+
+    /// What the shell looks like before it is fired.
+    pub fn disguised(&self) -> Shell {
+        match self {
+            Shell::Imposter => Shell::Blank,
+            other => other.clone(),
+        }
+    }
+}
+
 //BeanBag round limits the player to only use one item
 //Russian Roulette item, play russian roulette for a turn instead of the shotgun
 
@@ -48,76 +91,73 @@ impl Shotgun {
     pub fn new() -> Shotgun {
         Shotgun {
             shells: RefCell::new(Vec::new()),
+            loaded: RefCell::new(Vec::new()),
+            fired: RefCell::new(Vec::new()),
             state: ShotgunState::Default,
             model: ShotgunModel::Default,
         }
     }
 
-    pub fn load(&self,all_shells: Vec<Shell>, weights: Vec<usize>, num_shells: usize) {
-        let mut rng = thread_rng();
+    /// Loads `num_shells` shells dealt from the pool.
+    pub fn load(&self, rng: &mut impl Rng, pool: &ShellPool, num_shells: usize) {
         let mut shells = self.shells.borrow_mut();
-        shells.clear();
+        *shells = pool.deal(rng, num_shells);
 
-        let dist = WeightedIndex::new(&weights)
-            .expect("weights can not be zero or negative");
+        *self.loaded.borrow_mut() = shells.iter().rev().cloned().collect();
+        self.fired.borrow_mut().clear();
+    }
 
-        for _ in 0..num_shells {
-            let idx = dist.sample(&mut rng);
-            let random_shell = all_shells[idx].clone();
-            shells.push(random_shell);
+    /// Fires the next shell, `None` when the shotgun is empty.
+    pub fn shoot(&self) -> Option<Shell> {
+        let shell = self.shells.borrow_mut().pop()?;
+        self.fired.borrow_mut().push(shell.clone());
+        Some(shell)
+    }
+
+    /// Position in the round of the shell that fires next, counting from the first one loaded.
+    pub fn position(&self) -> usize {
+        self.fired.borrow().len()
+    }
+
+    /// The shell at a position in the round, if it is still in the chamber.
+    pub fn peek(&self, position: usize) -> Option<Shell> {
+        let idx = self.index(position)?;
+        Some(self.shells.borrow()[idx].clone())
+    }
+
+    /// Replaces the shell at a position in the round, for cheats and debugging.
+    pub fn set(&self, position: usize, shell: Shell) {
+        if let Some(idx) = self.index(position) {
+            self.shells.borrow_mut()[idx] = shell;
         }
+    }
 
-        if !shells.is_empty(){
-            if !shells.contains(&Shell::Blank) {
-                let num: usize = rng.gen_range(0..shells.len());
-                shells[num] = Shell::Blank;
-            }
+    /// Swaps two shells still in the chamber.
+    pub fn swap(&self, a: usize, b: usize) {
+        if let (Some(a), Some(b)) = (self.index(a), self.index(b)) {
+            self.shells.borrow_mut().swap(a, b);
         }
     }
 
-    pub fn load_random_shells(&self, num_shells: usize) {
-        let all_shells = vec![
-            Shell::Live,
-            Shell::Blank,
-            Shell::Poison,
-            Shell::BeanBag,
-            Shell::Taser,
-            Shell::Imposter,
-        ];
-
-        let weights = vec![
-            10, //Live
-            14, //Blank
-            1, //Poison
-            2, //BeanBag
-            1, //Taser
-            1, //Imposter
-        ];
-
-
-        self.load(all_shells, weights, num_shells);
+    //where a position in the round is in `shells`
+    fn index(&self, position: usize) -> Option<usize> {
+        let ahead = position.checked_sub(self.position())?;
+        self.shell_count().checked_sub(ahead + 1)
     }
 
-    pub fn load_default_shells(&self, num_shells: usize) {
-        let all_shells = vec![
-            Shell::Live,
-            Shell::Blank,
-        ];
-
-        let weights = vec![
-            10, //Live
-            14, //Blank
-        ];
-        self.load(all_shells, weights, num_shells);
+    /// Live, blank and special shells loaded this round, imposters are counted as blanks.
+    pub fn tally(&self) -> (usize, usize, usize) {
+        let loaded = self.loaded.borrow();
+        let live = loaded.iter().filter(|shell| **shell == Shell::Live).count();
+        let blank = loaded.iter().filter(|shell| matches!(shell, Shell::Blank | Shell::Imposter)).count();
+        (live, blank, loaded.len() - live - blank)
     }
 
-    pub fn shoot(&self) -> Option<String>{
-        let mut shell_borrow = self.shells.borrow_mut();
-        if let Some(popped_shell) = shell_borrow.pop() {
-            Some(format!("Popped shell: {:?}", popped_shell))
-        } else {
-            Some("No shell in shotgun.".to_string())
-        }
+    /// Empties the shotgun and forgets the round.
+    pub fn unload(&self) {
+        self.shells.borrow_mut().clear();
+        self.loaded.borrow_mut().clear();
+        self.fired.borrow_mut().clear();
     }
 
     pub fn shell_count(&self) -> usize {
@@ -0,0 +1,213 @@
+//turn_timer.rs
+use crate::event::TICK_FPS;
+
+/// Counts down how long the active player has left, in game ticks.
+///
+/// After a load there is a grace window where nobody can shoot yet, the turn clock only starts
+/// running once it is over. The grace window is there with or without a turn limit, the clock
+/// only runs with one.
+#[derive(Debug, Clone)]
+pub struct TurnTimer {
+    //seconds a turn lasts and seconds after a load before the first shot
+    limit_secs: Option<u32>,
+    grace_secs: u32,
+    //game ticks per second, what the budgets below are counted in
+    tick_fps: f64,
+    limit: Option<u32>,
+    grace: u32,
+    remaining: u32,
+    grace_left: u32,
+    running: bool,
+}
+
+impl Default for TurnTimer {
+    fn default() -> Self {
+        TurnTimer::new(None, 0, TICK_FPS)
+    }
+}
+
+impl TurnTimer {
+    /// `limit` and `grace` are in seconds, `tick_fps` is the tick rate they are counted at.
+    pub fn new(limit: Option<u32>, grace: u32, tick_fps: f64) -> TurnTimer {
+        TurnTimer {
+            limit_secs: limit,
+            grace_secs: grace,
+            tick_fps,
+            limit: limit.map(|secs| ticks(secs, tick_fps)),
+            grace: ticks(grace, tick_fps),
+            remaining: 0,
+            grace_left: 0,
+            running: false,
+        }
+    }
+
+    /// The tick rate changed, a running clock keeps the time it had left.
+    pub fn set_tick_rate(&mut self, tick_fps: f64) {
+        if tick_fps == self.tick_fps || tick_fps <= 0.0 {
+            return;
+        }
+        let rescale = |left: u32| (left as f64 * tick_fps / self.tick_fps).round() as u32;
+        self.remaining = rescale(self.remaining);
+        self.grace_left = rescale(self.grace_left);
+        self.limit = self.limit_secs.map(|secs| ticks(secs, tick_fps));
+        self.grace = ticks(self.grace_secs, tick_fps);
+        self.tick_fps = tick_fps;
+    }
+
+    pub fn is_enabled(&self) -> bool {
+        self.limit.is_some()
+    }
+
+    pub fn is_running(&self) -> bool {
+        self.running
+    }
+
+    /// The shotgun was loaded, the grace window starts and then the first turn.
+    pub fn start_round(&mut self) {
+        self.grace_left = self.grace;
+        self.start_turn();
+    }
+
+    /// Fills the clock back up for the next turn, a grace window that is still open stays open.
+    pub fn start_turn(&mut self) {
+        if let Some(limit) = self.limit {
+            self.remaining = limit;
+            self.running = true;
+        }
+    }
+
+    pub fn stop(&mut self) {
+        self.running = false;
+        self.grace_left = 0;
+    }
+
+    /// Counts one tick off, returns true on the tick the turn runs out.
+    pub fn tick(&mut self) -> bool {
+        if self.grace_left > 0 {
+            self.grace_left -= 1;
+            return false;
+        }
+        if !self.running {
+            return false;
+        }
+        self.remaining = self.remaining.saturating_sub(1);
+        if self.remaining == 0 {
+            self.running = false;
+            return true;
+        }
+        false
+    }
+
+    /// Shots have to wait until this is over.
+    pub fn in_grace(&self) -> bool {
+        self.grace_left > 0
+    }
+
+    /// Whole seconds left in the grace window, rounded up.
+    pub fn grace_seconds(&self) -> u32 {
+        seconds(self.grace_left, self.tick_fps)
+    }
+
+    /// Whole seconds left in the turn, rounded up.
+    pub fn seconds_left(&self) -> u32 {
+        seconds(self.remaining, self.tick_fps)
+    }
+
+    /// How much of the turn is left, from 1 down to 0.
+    pub fn ratio(&self) -> f64 {
+        match self.limit {
+            Some(limit) if limit > 0 => self.remaining as f64 / limit as f64,
+            _ => 0.0,
+        }
+    }
+}
+
+fn ticks(seconds: u32, tick_fps: f64) -> u32 {
+    (seconds as f64 * tick_fps).round() as u32
+}
+
+fn seconds(ticks: u32, tick_fps: f64) -> u32 {
+    (ticks as f64 / tick_fps).ceil() as u32
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn run(timer: &mut TurnTimer, ticks: u32) -> bool {
+        (0..ticks).fold(false, |ran_out, _| timer.tick() || ran_out)
+    }
+
+    #[test]
+    fn runs_out_after_the_limit() {
+        let mut timer = TurnTimer::new(Some(2), 0, 10.0);
+        timer.start_round();
+        assert!(timer.is_running());
+        assert_eq!(timer.seconds_left(), 2);
+        assert!(!run(&mut timer, 19));
+        assert_eq!(timer.seconds_left(), 1);
+        assert!(timer.tick());
+        assert!(!timer.is_running());
+        assert_eq!(timer.ratio(), 0.0);
+    }
+
+    #[test]
+    fn the_clock_waits_for_the_grace_window() {
+        let mut timer = TurnTimer::new(Some(1), 2, 10.0);
+        timer.start_round();
+        assert!(timer.in_grace());
+        assert_eq!(timer.grace_seconds(), 2);
+        run(&mut timer, 20);
+        assert!(!timer.in_grace());
+        assert_eq!(timer.ratio(), 1.0);
+        assert!(run(&mut timer, 10));
+    }
+
+    #[test]
+    fn grace_counts_down_without_a_limit() {
+        let mut timer = TurnTimer::new(None, 1, 30.0);
+        timer.start_round();
+        assert!(!timer.is_enabled());
+        assert!(timer.in_grace());
+        assert!(!run(&mut timer, 30));
+        assert!(!timer.in_grace());
+        assert!(!timer.is_running());
+    }
+
+    #[test]
+    fn a_new_turn_keeps_an_open_grace_window() {
+        let mut timer = TurnTimer::new(Some(5), 1, 10.0);
+        timer.start_round();
+        run(&mut timer, 5);
+        timer.start_turn();
+        assert!(timer.in_grace());
+        assert_eq!(timer.seconds_left(), 5);
+    }
+
+    #[test]
+    fn stopping_ends_the_grace_window() {
+        let mut timer = TurnTimer::new(Some(5), 3, 10.0);
+        timer.start_round();
+        timer.stop();
+        assert!(!timer.in_grace());
+        assert!(!timer.is_running());
+        assert!(!run(&mut timer, 100));
+    }
+
+    #[test]
+    fn changing_the_tick_rate_keeps_the_time_left() {
+        let mut timer = TurnTimer::new(Some(10), 2, 60.0);
+        timer.start_round();
+        run(&mut timer, 60);
+        assert_eq!(timer.grace_seconds(), 1);
+
+        timer.set_tick_rate(15.0);
+        assert_eq!(timer.grace_seconds(), 1);
+        assert_eq!(timer.seconds_left(), 10);
+        run(&mut timer, 15);
+        assert!(!timer.in_grace());
+        //ten seconds at the new rate
+        assert!(!run(&mut timer, 149));
+        assert!(timer.tick());
+    }
+}
@@ -1,17 +1,129 @@
 //data.rs
 
-use crate::components::shotgun::{Shotgun};
+use rand::Rng;
+use serde::{Deserialize, Serialize};
+
+use crate::components::items::Items;
+use crate::components::mode::ModeRules;
+use crate::components::player::{Player, Status};
+use crate::components::setup::GameSetup;
+use crate::components::shotgun::{Shell, Shotgun, ShotgunState};
 
 //need to implement things which would allow default and clone
-#[derive(Debug, Default, Clone)]
+#[derive(Debug, Default, Clone, Serialize, Deserialize)]
 pub struct Data {
     pub shotgun: Shotgun,
+    pub players: Vec<Player>,
+    /// what the match was started with, a new match at the same table starts from it again
+    #[serde(default)]
+    pub setup: GameSetup,
+    /// the mode's rules as they were when the match started
+    #[serde(default)]
+    pub rules: ModeRules,
 }
 
 impl Data {
     pub fn new() -> Self {
+        Self::from_setup(GameSetup::new(), ModeRules::default())
+    }
+
+    /// A fresh table as picked in the New Game wizard.
+    pub fn from_setup(setup: GameSetup, rules: ModeRules) -> Self {
         Self {
             shotgun: Shotgun::new(),
+            players: rules.players(&setup.seats),
+            setup,
+            rules,
+        }
+    }
+
+    /// The next living player after `idx`, wrapping around.
+    pub fn next_alive(&self, idx: usize) -> Option<usize> {
+        let len = self.players.len();
+        (1..len)
+            .map(|step| (idx + step) % len)
+            .find(|other| self.players[*other].is_alive())
+    }
+
+    /// What a fired shell does to the player it hit.
+    pub fn hit(&mut self, shell: &Shell, shooter: usize, target: usize) {
+        let damage = if matches!(self.shotgun.state, ShotgunState::SawedOff) { 2 } else { 1 };
+        match shell {
+            Shell::Live | Shell::Imposter => self.players[target].damage(damage),
+            //goes off in the shooter's hands
+            Shell::SelfDestruct => self.players[shooter].damage(damage),
+            Shell::Poison => self.players[target].add_status(Status::Poisoned),
+            Shell::BeanBag | Shell::Taser => self.players[target].add_status(Status::Stunned),
+            Shell::Blank => {},
+        }
+        //the saw only lasts one shot
+        if matches!(self.shotgun.state, ShotgunState::SawedOff) {
+            self.shotgun.state = ShotgunState::Default;
+        }
+    }
+
+    /// Uses one of the player's items, returns what happened for the log.
+    pub fn use_item(&mut self, rng: &mut impl Rng, player: usize, item: Items) -> Option<String> {
+        if !self.players[player].take_item(item) {
+            return None;
         }
+        let name = self.players[player].name.clone();
+        let position = self.shotgun.position();
+        let msg = match item {
+            Items::Saw => {
+                self.shotgun.state = ShotgunState::SawedOff;
+                format!("{name} saws off the barrel.")
+            },
+            Items::Beer => match self.shotgun.shoot() {
+                Some(shell) => format!("{name} racks the shotgun, out comes a {shell:?} shell."),
+                None => format!("{name} racks an empty shotgun."),
+            },
+            Items::Cigarette => {
+                self.players[player].heal(1);
+                format!("{name} smokes a cigarette.")
+            },
+            Items::Meth => {
+                self.players[player].heal(2);
+                format!("{name} takes meth.")
+            },
+            Items::Inverter => {
+                if let Some(next) = self.shotgun.shells.borrow_mut().last_mut() {
+                    match next {
+                        Shell::Live => *next = Shell::Blank,
+                        Shell::Blank => *next = Shell::Live,
+                        _ => {},
+                    }
+                }
+                //whatever anyone saw there is no longer true
+                for other in self.players.iter_mut() {
+                    other.known.retain(|(p, _)| *p != position);
+                }
+                format!("{name} uses the inverter.")
+            },
+            Items::MagnifyingGlass => {
+                if let Some(shell) = self.shotgun.peek(position) {
+                    self.players[player].reveal(position, shell.disguised());
+                }
+                format!("{name} looks down the barrel.")
+            },
+            Items::LSD => {
+                let ahead = self.shotgun.shell_count();
+                if ahead > 1 {
+                    let seen = position + rng.gen_range(1..ahead);
+                    if let Some(shell) = self.shotgun.peek(seen) {
+                        self.players[player].reveal(seen, shell.disguised());
+                    }
+                }
+                format!("{name} takes LSD and sees the future.")
+            },
+            Items::Handcuffs => {
+                if let Some(next) = self.next_alive(player) {
+                    self.players[next].add_status(Status::Cuffed);
+                }
+                format!("{name} cuffs the next player.")
+            },
+            Items::Mirror | Items::AED | Items::Adrenaline => format!("{name} uses {}.", item.name()),
+        };
+        Some(msg)
     }
 }
@@ -2,12 +2,46 @@ use color_eyre::eyre::OptionExt;
 use futures::{FutureExt, StreamExt};
 use ratatui::crossterm::event::Event as CrosstermEvent;
 use std::time::Duration;
-use tokio::sync::mpsc;
+use tokio::sync::{mpsc, watch};
 
-use crate::components::enums::ReloadAmount;
+use crate::components::enums::{ConfirmAction, ReloadAmount};
+use crate::uihelp::inspector::{Cheat, InspectorAction};
+use crate::uihelp::log_view::LogAction;
+use crate::uihelp::pause::MenuAction;
+use crate::uihelp::widget_data::WidgetKind;
 
-/// The frequency at which tick events are emitted.
-const TICK_FPS: f64 = 30.0;
+/// The frequency at which tick events are emitted, unless changed with [`EventHandler::set_tick_rate`].
+pub const TICK_FPS: f64 = 30.0;
+/// The frequency at which frame events are emitted, unless changed with [`EventHandler::set_frame_rate`].
+pub const FRAME_FPS: f64 = 60.0;
+
+/// How often ticks and frames are sent, shared with the event task so it can change at runtime.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Rates {
+    /// game ticks per second, animations and timers count these
+    pub tick_fps: f64,
+    /// chances to draw per second
+    pub frame_fps: f64,
+    /// no ticks are sent while paused, frames and input keep coming
+    pub paused: bool,
+}
+
+impl Default for Rates {
+    fn default() -> Self {
+        Rates { tick_fps: TICK_FPS, frame_fps: FRAME_FPS, paused: false }
+    }
+}
+
+impl Rates {
+    //anything outside this would either never fire or flood the channel, and nan has no period
+    fn clamp(fps: f64) -> f64 {
+        if fps.is_nan() { 1.0 } else { fps.clamp(1.0, 240.0) }
+    }
+
+    fn period(fps: f64) -> Duration {
+        Duration::from_secs_f64(1.0 / fps)
+    }
+}
 
 /// Representation of all possible events.
 #[derive(Clone, Debug)]
@@ -15,9 +49,11 @@ pub enum Event {
     /// An event that is emitted on a regular schedule.
     ///
     /// Use this event to run any code which has to run outside of being a direct response to a user
-    /// event. e.g. polling exernal systems, updating animations, or rendering the UI based on a
-    /// fixed frame rate.
+    /// event. e.g. polling exernal systems, updating animations or counting down timers. It stops
+    /// while ticks are paused.
     Tick,
+    /// Time to draw the screen, if anything changed since the last one.
+    Frame,
     /// Crossterm events.
     ///
     /// These events are emitted by the terminal.
@@ -41,29 +77,63 @@ pub enum AppEvent {
     Reload(ReloadAmount),
     /// Shoot
     Shoot,
+    /// Shoot yourself
+    ShootSelf,
+    /// Give up the match
+    Forfeit,
+    /// The turn clock ran out
+    TurnTimeout,
 
 
     ///UI EVENTS
-   /// Popup screen
-    ShowData,
-    /// Show log
-    ShowLog,
-    /// Show inventory
-    ShowInventory,
-    /// Show player
-    ShowPlayer,
+    /// Open the pause menu and stop the game ticks
+    Pause,
+    /// Close the pause menu and let the game run again
+    Resume,
+    /// Move around the pause menu
+    Menu(MenuAction),
+    /// Move around the title screen and the New Game wizard
+    Title(MenuAction),
+    /// Ask before doing something that can't be undone
+    Ask(ConfirmAction),
+    /// What was answered in the confirm dialog, true to go ahead
+    Answer(ConfirmAction, bool),
+    /// Show or hide a widget
+    ToggleWidget(WidgetKind),
+    /// Hide a widget, focus goes back to the one before it
+    CloseWidget(WidgetKind),
+    /// Focus a widget and bring it to the front
+    FocusWidget(WidgetKind),
+    /// Send a widget behind the others
+    LowerWidget(WidgetKind),
     /// Show shotgun
     FocusShotgun,
-    /// Scroll the log up
-    ScrollUp,
-    /// Scroll the log down
-    ScrollDown,
+    /// Select an inventory slot
+    SelectItem(usize),
+    /// Use the item in an inventory slot
+    UseItem(usize),
+    /// Aim at a player
+    SelectTarget(usize),
+    /// Aim at the next opponent
+    NextTarget,
+    /// Move around the data inspector
+    Inspect(InspectorAction),
+    /// Change the game state from the inspector, only with --cheats
+    Cheat(Cheat),
+    /// Scroll or search the log
+    Log(LogAction),
     /// Change focus
     ChangeFocus,
     /// Change focus back
     ChangeFocusBack,
 }
 
+impl Default for EventHandler {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
 /// Terminal event handler.
 #[derive(Debug)]
 pub struct EventHandler {
@@ -71,15 +141,40 @@ pub struct EventHandler {
     sender: mpsc::UnboundedSender<Event>,
     /// Event receiver channel.
     receiver: mpsc::UnboundedReceiver<Event>,
+    /// Tick and frame rates the event task runs at.
+    rates: watch::Sender<Rates>,
 }
 
 impl EventHandler {
     /// Constructs a new instance of [`EventHandler`] and spawns a new thread to handle events.
     pub fn new() -> Self {
         let (sender, receiver) = mpsc::unbounded_channel();
-        let actor = EventTask::new(sender.clone());
+        let (rates, rates_receiver) = watch::channel(Rates::default());
+        let actor = EventTask::new(sender.clone(), rates_receiver);
         tokio::spawn(async { actor.run().await });
-        Self { sender, receiver }
+        Self { sender, receiver, rates }
+    }
+
+    /// The rates the event task runs at, after clamping.
+    pub fn rates(&self) -> Rates {
+        *self.rates.borrow()
+    }
+
+    /// Changes how many game ticks are sent per second, between 1 and 240.
+    pub fn set_tick_rate(&self, fps: f64) {
+        let fps = Rates::clamp(fps);
+        self.rates.send_if_modified(|rates| std::mem::replace(&mut rates.tick_fps, fps) != fps);
+    }
+
+    /// Changes how many frames are sent per second, between 1 and 240.
+    pub fn set_frame_rate(&self, fps: f64) {
+        let fps = Rates::clamp(fps);
+        self.rates.send_if_modified(|rates| std::mem::replace(&mut rates.frame_fps, fps) != fps);
+    }
+
+    /// Stops or restarts the game ticks, frames keep coming either way.
+    pub fn pause_ticks(&self, paused: bool) {
+        self.rates.send_if_modified(|rates| std::mem::replace(&mut rates.paused, paused) != paused);
     }
 
     /// Receives an event from the sender.
@@ -109,35 +204,55 @@ impl EventHandler {
     }
 }
 
-/// A thread that handles reading crossterm events and emitting tick events on a regular schedule.
+/// A thread that handles reading crossterm events and emitting tick and frame events on a regular schedule.
 struct EventTask {
     /// Event sender channel.
     sender: mpsc::UnboundedSender<Event>,
+    /// Rates set through the [`EventHandler`].
+    rates: watch::Receiver<Rates>,
 }
 
 impl EventTask {
     /// Constructs a new instance of [`EventThread`].
-    fn new(sender: mpsc::UnboundedSender<Event>) -> Self {
-        Self { sender }
+    fn new(sender: mpsc::UnboundedSender<Event>, rates: watch::Receiver<Rates>) -> Self {
+        Self { sender, rates }
     }
 
     /// Runs the event thread.
     ///
-    /// This function emits tick events at a fixed rate and polls for crossterm events in between.
-    async fn run(self) -> color_eyre::Result<()> {
-        let tick_rate = Duration::from_secs_f64(1.0 / TICK_FPS);
+    /// This function emits tick and frame events at their own rates and polls for crossterm events
+    /// in between.
+    async fn run(mut self) -> color_eyre::Result<()> {
+        let mut rates = *self.rates.borrow_and_update();
         let mut reader = crossterm::event::EventStream::new();
-        let mut tick = tokio::time::interval(tick_rate);
+        let mut tick = tokio::time::interval(Rates::period(rates.tick_fps));
+        let mut frame = tokio::time::interval(Rates::period(rates.frame_fps));
         loop {
-            let tick_delay = tick.tick();
             let crossterm_event = reader.next().fuse();
             tokio::select! {
               _ = self.sender.closed() => {
                 break;
               }
-              _ = tick_delay => {
+              Ok(()) = self.rates.changed() => {
+                let new = *self.rates.borrow_and_update();
+                if new.tick_fps != rates.tick_fps {
+                    tick = tokio::time::interval(Rates::period(new.tick_fps));
+                }
+                if new.frame_fps != rates.frame_fps {
+                    frame = tokio::time::interval(Rates::period(new.frame_fps));
+                }
+                //don't make up for the ticks missed while paused
+                if rates.paused && !new.paused {
+                    tick.reset();
+                }
+                rates = new;
+              }
+              _ = tick.tick(), if !rates.paused => {
                 self.send(Event::Tick);
               }
+              _ = frame.tick() => {
+                self.send(Event::Frame);
+              }
               Some(Ok(evt)) = crossterm_event => {
                 self.send(Event::Crossterm(evt));
               }
@@ -0,0 +1,163 @@
+//keymap.rs
+use std::collections::HashMap;
+
+use serde::{Deserialize, Serialize};
+
+use crate::uihelp::registry;
+
+/// Game actions that can be rebound in the settings menu.
+///
+/// Panel hotkeys come from the registry and aren't in here, Esc always pauses so the menu can't
+/// be locked away.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum KeyAction {
+    Shoot,
+    ShootSelf,
+    NextTarget,
+    FocusShotgun,
+    Forfeit,
+    Reload,
+    Pause,
+}
+
+impl KeyAction {
+    pub const ALL: [KeyAction; 7] = [
+        KeyAction::Shoot,
+        KeyAction::ShootSelf,
+        KeyAction::NextTarget,
+        KeyAction::FocusShotgun,
+        KeyAction::Forfeit,
+        KeyAction::Reload,
+        KeyAction::Pause,
+    ];
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            KeyAction::Shoot => "Shoot",
+            KeyAction::ShootSelf => "Shoot yourself",
+            KeyAction::NextTarget => "Aim at the next player",
+            KeyAction::FocusShotgun => "Focus shotgun",
+            KeyAction::Forfeit => "Forfeit",
+            KeyAction::Reload => "Load the shotgun",
+            KeyAction::Pause => "Pause",
+        }
+    }
+
+    fn default_key(&self) -> char {
+        match self {
+            KeyAction::Shoot => ' ',
+            KeyAction::ShootSelf => 'm',
+            KeyAction::NextTarget => 'a',
+            KeyAction::FocusShotgun => 's',
+            KeyAction::Forfeit => 'f',
+            KeyAction::Reload => 'r',
+            KeyAction::Pause => 'q',
+        }
+    }
+}
+
+/// Which key does what, letters match either case.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(transparent)]
+pub struct Keymap {
+    bindings: HashMap<KeyAction, char>,
+}
+
+impl Default for Keymap {
+    fn default() -> Self {
+        Keymap { bindings: KeyAction::ALL.iter().map(|action| (*action, action.default_key())).collect() }
+    }
+}
+
+impl Keymap {
+    pub fn key(&self, action: KeyAction) -> char {
+        self.bindings.get(&action).copied().unwrap_or(action.default_key())
+    }
+
+    /// The action bound to a key, if any.
+    pub fn action(&self, key: char) -> Option<KeyAction> {
+        KeyAction::ALL.into_iter().find(|action| self.key(*action).eq_ignore_ascii_case(&key))
+    }
+
+    /// Binds `key` to `action`, an action that had the key before gets the old key of `action`.
+    ///
+    /// Panel hotkeys and digits belong to other things and are refused.
+    pub fn bind(&mut self, action: KeyAction, key: char) -> Result<(), String> {
+        if key.is_ascii_digit() {
+            return Err(String::from("digits select items"));
+        }
+        if let Some(spec) = registry::by_hotkey(key) {
+            return Err(format!("'{key}' already toggles the {:?} panel", spec.kind));
+        }
+        let old = self.key(action);
+        if let Some(other) = self.action(key) {
+            self.bindings.insert(other, old);
+        }
+        self.bindings.insert(action, key);
+        Ok(())
+    }
+
+    /// How a key is shown in the settings menu.
+    pub fn label(key: char) -> String {
+        match key {
+            ' ' => String::from("space"),
+            key => key.to_string(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn default_keys_are_unique_and_free() {
+        let keymap = Keymap::default();
+        for action in KeyAction::ALL {
+            let key = keymap.key(action);
+            assert_eq!(keymap.action(key), Some(action));
+            assert!(registry::by_hotkey(key).is_none(), "{key}");
+        }
+    }
+
+    #[test]
+    fn letters_match_either_case() {
+        let keymap = Keymap::default();
+        assert_eq!(keymap.action('R'), Some(KeyAction::Reload));
+        assert_eq!(keymap.action('r'), Some(KeyAction::Reload));
+    }
+
+    #[test]
+    fn binding_a_taken_key_swaps_the_two() {
+        let mut keymap = Keymap::default();
+        assert_eq!(keymap.bind(KeyAction::Shoot, 'r'), Ok(()));
+        assert_eq!(keymap.key(KeyAction::Shoot), 'r');
+        assert_eq!(keymap.key(KeyAction::Reload), ' ');
+        assert_eq!(keymap.action(' '), Some(KeyAction::Reload));
+    }
+
+    #[test]
+    fn binding_a_free_key_leaves_the_rest() {
+        let mut keymap = Keymap::default();
+        assert_eq!(keymap.bind(KeyAction::Pause, 'z'), Ok(()));
+        assert_eq!(keymap.action('z'), Some(KeyAction::Pause));
+        assert_eq!(keymap.action('q'), None);
+        assert_eq!(keymap.key(KeyAction::Forfeit), 'f');
+    }
+
+    #[test]
+    fn digits_and_panel_hotkeys_are_refused() {
+        let mut keymap = Keymap::default();
+        assert!(keymap.bind(KeyAction::Shoot, '3').is_err());
+        assert!(keymap.bind(KeyAction::Shoot, 'l').unwrap_err().contains("Log"));
+        assert_eq!(keymap, Keymap::default());
+    }
+
+    #[test]
+    fn actions_missing_from_a_saved_keymap_keep_their_default() {
+        let keymap: Keymap = toml::from_str("shoot = \"x\"").unwrap();
+        assert_eq!(keymap.key(KeyAction::Shoot), 'x');
+        assert_eq!(keymap.key(KeyAction::NextTarget), 'a');
+    }
+}
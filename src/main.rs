@@ -1,4 +1,5 @@
 use crate::app::App;
+use crate::settings::Settings;
 
 pub mod event;
 pub mod ui;
@@ -6,12 +7,20 @@ pub mod components;
 pub mod uihelp;
 pub mod data;
 pub mod app;
+pub mod settings;
+pub mod assets;
+pub mod theme;
+pub mod keymap;
+pub mod save;
+pub mod catalog;
+pub mod util;
 
 #[tokio::main]
 async fn main() -> color_eyre::Result<()> {
     color_eyre::install()?;
     let terminal = ratatui::init();
-    let result = App::new().run(terminal).await;
+    let settings = Settings::from_args(std::env::args().skip(1));
+    let result = App::with_settings(settings).run(terminal).await;
     ratatui::restore();
     result
 }
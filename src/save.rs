@@ -0,0 +1,35 @@
+//save.rs
+use std::path::{Path, PathBuf};
+
+use color_eyre::eyre::WrapErr;
+use serde::{Deserialize, Serialize};
+
+use crate::components::match_data::MatchData;
+use crate::data::Data;
+use crate::uihelp::log_view::LogEntry;
+
+/// A match in progress, as written by Save in the pause menu.
+#[derive(Debug, Serialize, Deserialize)]
+pub struct SaveGame {
+    pub data: Data,
+    pub match_data: MatchData,
+    pub log: Vec<LogEntry>,
+}
+
+/// `save.toml` in the config directory, there is only one save slot.
+pub fn save_path() -> Option<PathBuf> {
+    crate::settings::config_dir().map(|dir| dir.join("save.toml"))
+}
+
+pub fn save_game(path: &Path, save: &SaveGame) -> color_eyre::Result<()> {
+    let content = toml::to_string(save).wrap_err("could not write save")?;
+    if let Some(dir) = path.parent() {
+        std::fs::create_dir_all(dir)?;
+    }
+    std::fs::write(path, content).wrap_err("could not save the game")
+}
+
+pub fn load_game(path: &Path) -> color_eyre::Result<SaveGame> {
+    let content = std::fs::read_to_string(path).wrap_err("could not read save")?;
+    toml::from_str(&content).wrap_err("invalid save file")
+}
@@ -0,0 +1,189 @@
+//settings.rs
+use std::path::{Path, PathBuf};
+
+use color_eyre::eyre::WrapErr;
+use serde::{Deserialize, Serialize};
+
+use crate::components::enums::TimeoutAction;
+use crate::util;
+use crate::event::{FRAME_FPS, TICK_FPS};
+use crate::keymap::Keymap;
+use crate::theme::Theme;
+
+/// Seconds after a load before anyone can shoot.
+pub const DEFAULT_GRACE: u32 = 10;
+
+/// Where settings and saved layouts live, `$XDG_CONFIG_HOME/tui-roulette` or `~/.config/tui-roulette`.
+pub fn config_dir() -> Option<PathBuf> {
+    let base = std::env::var_os("XDG_CONFIG_HOME")
+        .map(PathBuf::from)
+        .or_else(|| std::env::var_os("HOME").map(|home| PathBuf::from(home).join(".config")))?;
+    Some(base.join("tui-roulette"))
+}
+
+/// Animation speeds the settings menu steps through.
+pub const ANIMATION_SPEEDS: [f32; 4] = [0.5, 1.0, 1.5, 2.0];
+/// Tick and frame rates the settings menu steps through.
+pub const TICK_RATES: [f64; 4] = [15.0, 30.0, 60.0, 120.0];
+pub const FRAME_RATES: [f64; 4] = [30.0, 60.0, 120.0, 144.0];
+
+/// Choices made in the settings menu, kept in `settings.toml` between sessions.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct Preferences {
+    pub theme: Theme,
+    /// how fast effects play, 1 is normal
+    pub animation_speed: f32,
+    pub keymap: Keymap,
+    /// game ticks per second, timers and animations take as long either way
+    pub tick_rate: f64,
+    /// most frames drawn per second
+    pub frame_rate: f64,
+}
+
+impl Default for Preferences {
+    fn default() -> Self {
+        Preferences {
+            theme: Theme::default(),
+            animation_speed: 1.0,
+            keymap: Keymap::default(),
+            tick_rate: TICK_FPS,
+            frame_rate: FRAME_FPS,
+        }
+    }
+}
+
+impl Preferences {
+    /// The next speed in [`ANIMATION_SPEEDS`], wrapping around.
+    pub fn cycle_speed(&mut self, forward: bool) {
+        self.animation_speed = util::cycle(&ANIMATION_SPEEDS, self.animation_speed, forward);
+    }
+
+    /// The next rate in [`TICK_RATES`], a rate off the list starts from the first one.
+    pub fn cycle_tick_rate(&mut self, forward: bool) {
+        self.tick_rate = util::cycle(&TICK_RATES, self.tick_rate, forward);
+    }
+
+    pub fn cycle_frame_rate(&mut self, forward: bool) {
+        self.frame_rate = util::cycle(&FRAME_RATES, self.frame_rate, forward);
+    }
+}
+
+/// `settings.toml` in the config directory.
+pub fn preferences_path() -> Option<PathBuf> {
+    config_dir().map(|dir| dir.join("settings.toml"))
+}
+
+/// Reads the saved preferences, a missing file gives the defaults.
+pub fn load_preferences(path: &Path) -> color_eyre::Result<Preferences> {
+    if !path.exists() {
+        return Ok(Preferences::default());
+    }
+    let content = std::fs::read_to_string(path).wrap_err("could not read settings")?;
+    toml::from_str(&content).wrap_err("invalid settings file")
+}
+
+pub fn save_preferences(path: &Path, preferences: &Preferences) -> color_eyre::Result<()> {
+    let content = toml::to_string(preferences)?;
+    if let Some(dir) = path.parent() {
+        std::fs::create_dir_all(dir)?;
+    }
+    std::fs::write(path, content).wrap_err("could not save settings")
+}
+
+/// Player preferences, from the command line and the settings menu.
+#[derive(Debug, Default, Clone)]
+pub struct Settings {
+    /// no screen shake or flicker, effects show a single still frame
+    pub reduce_motion: bool,
+    /// draw the svg artwork instead of the ascii art when idle
+    pub vector_art: bool,
+    /// directory of an extra asset pack
+    pub pack: Option<PathBuf>,
+    /// lets the data inspector edit the shells and health
+    pub cheats: bool,
+    /// game ticks per second for this session, in place of the one in the settings menu
+    pub tick_rate: Option<f64>,
+    /// most frames drawn per second for this session
+    pub frame_rate: Option<f64>,
+    /// seconds a player has for their turn, no clock when not set
+    pub turn_time: Option<u32>,
+    /// seconds after a load before the first shot, [`DEFAULT_GRACE`] when not set
+    pub grace: Option<u32>,
+    /// what the clock does when a turn runs out
+    pub on_timeout: TimeoutAction,
+    /// what was picked in the settings menu
+    pub preferences: Preferences,
+}
+
+impl Settings {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Game ticks per second in use, the command line over the settings menu.
+    pub fn tick_fps(&self) -> f64 {
+        self.tick_rate.unwrap_or(self.preferences.tick_rate)
+    }
+
+    /// Most frames per second in use, the command line over the settings menu.
+    pub fn frame_fps(&self) -> f64 {
+        self.frame_rate.unwrap_or(self.preferences.frame_rate)
+    }
+
+    pub fn from_args(mut args: impl Iterator<Item = String>) -> Self {
+        let mut settings = Self::new();
+        while let Some(arg) = args.next() {
+            match arg.as_str() {
+                "--reduce-motion" => settings.reduce_motion = true,
+                "--vector-art" => settings.vector_art = true,
+                "--pack" => settings.pack = args.next().map(PathBuf::from),
+                "--cheats" => settings.cheats = true,
+                "--tick-rate" => settings.tick_rate = args.next().and_then(parse_rate),
+                "--frame-rate" => settings.frame_rate = args.next().and_then(parse_rate),
+                //a turn of no time is no clock at all
+                "--turn-time" => {
+                    settings.turn_time = args.next().and_then(|secs| secs.parse().ok()).filter(|secs| *secs > 0)
+                },
+                "--grace" => settings.grace = args.next().and_then(|secs| secs.parse().ok()),
+                "--on-timeout" => {
+                    settings.on_timeout = match args.next().as_deref() {
+                        Some("shoot") => TimeoutAction::RandomShot,
+                        _ => TimeoutAction::PassTurn,
+                    }
+                },
+                _ => {},
+            }
+        }
+        settings
+    }
+}
+
+//nan and inf parse as numbers but make no sense as a period
+fn parse_rate(fps: String) -> Option<f64> {
+    fps.parse().ok().filter(|fps: &f64| fps.is_finite())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn parse(args: &[&str]) -> Settings {
+        Settings::from_args(args.iter().map(|arg| arg.to_string()))
+    }
+
+    #[test]
+    fn a_turn_time_of_zero_means_no_clock() {
+        assert_eq!(parse(&["--turn-time", "0"]).turn_time, None);
+        assert_eq!(parse(&["--turn-time", "15"]).turn_time, Some(15));
+    }
+
+    #[test]
+    fn rates_that_are_not_numbers_are_ignored() {
+        for rate in ["nan", "inf", "-inf", "fast"] {
+            assert_eq!(parse(&["--tick-rate", rate]).tick_rate, None);
+            assert_eq!(parse(&["--frame-rate", rate]).frame_rate, None);
+        }
+        assert_eq!(parse(&["--tick-rate", "30"]).tick_rate, Some(30.0));
+    }
+}
@@ -1,3 +1,69 @@
-use ratatui::style::{Color, Modifier, Style};
+//theme.rs
+use ratatui::style::Color;
+use serde::{Deserialize, Serialize};
+
+use crate::util;
 
 const DARK_BLUE: Color = Color::Rgb(16, 24, 48);
+
+/// Color scheme of the frame and the panels, picked in the settings menu.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Theme {
+    #[default]
+    Classic,
+    Midnight,
+    Mono,
+}
+
+/// The colors a theme is made of.
+#[derive(Debug, Clone, Copy)]
+pub struct Palette {
+    /// the frame around the whole game
+    pub border: Color,
+    /// borders of the focused panel and the selected entry
+    pub focus: Color,
+    /// things that are there but don't matter right now
+    pub muted: Color,
+    pub background: Option<Color>,
+}
+
+impl Theme {
+    pub const ALL: [Theme; 3] = [Theme::Classic, Theme::Midnight, Theme::Mono];
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            Theme::Classic => "Classic",
+            Theme::Midnight => "Midnight",
+            Theme::Mono => "Mono",
+        }
+    }
+
+    pub fn palette(&self) -> Palette {
+        match self {
+            Theme::Classic => Palette {
+                border: Color::Red,
+                focus: Color::LightRed,
+                muted: Color::DarkGray,
+                background: None,
+            },
+            Theme::Midnight => Palette {
+                border: Color::Blue,
+                focus: Color::LightCyan,
+                muted: Color::Gray,
+                background: Some(DARK_BLUE),
+            },
+            Theme::Mono => Palette {
+                border: Color::Gray,
+                focus: Color::White,
+                muted: Color::DarkGray,
+                background: None,
+            },
+        }
+    }
+
+    /// The theme after this one, wrapping around.
+    pub fn cycle(&self, forward: bool) -> Theme {
+        util::cycle(&Theme::ALL, *self, forward)
+    }
+}
@@ -1,19 +1,38 @@
-use std::rc::Rc;
+use std::sync::LazyLock;
 
 use ratatui::{
-    buffer::Buffer, symbols, layout::{Alignment, Rect}, prelude::*, style::{Color, Styled, Stylize}, widgets::{Block, BorderType, Borders, Clear, Paragraph, Widget, Wrap, canvas::Canvas, canvas::Line}
+    layout::Rect, prelude::*, style::{Color, Styled}, symbols::Marker, widgets::{Block, BorderType, Borders, Clear, LineGauge, Paragraph, Scrollbar, ScrollbarOrientation, ScrollbarState, Wrap}
 };
 
-//add svg crate
-/* use svg::{Tree, NodeKind}; */
-
-use crate::uihelp::widget_data::{WidgetKind, WidgetData};
+use crate::uihelp::widget_data::WidgetKind;
+use crate::uihelp::svg::SvgArt;
+use crate::uihelp::window;
+use crate::uihelp::confirm::Confirm;
+use crate::uihelp::grid::Grid;
+use crate::uihelp::inspector;
+use crate::uihelp::log_view::{self, LogView};
+use crate::uihelp::text;
+use crate::uihelp::pause::{self, PauseItem, SettingsRow};
+use crate::components::enums::{Menu, TitlePage};
+use crate::components::mode::{Rounds, CUSTOM};
+use crate::uihelp::title::{ReplayRow, SetupRow, TitleItem, TitleMenu};
+use crate::uihelp::pool_editor::{PoolRow, ShellField};
+use crate::keymap::Keymap;
+use crate::uihelp::screen::{self, ScreenSize};
+use crate::components::player::MAX_ITEMS;
+use crate::components::shotgun::Shell;
+use crate::uihelp::layout::{LayoutEngine, Placement};
+use crate::uihelp::registry::{self, REGISTRY};
 use crate::app::{ App };
 
-const PLAYER_ART: &str = r#"
- (\_/)
- ( •_•)
-/>🍪
+/// Shorter shotgun for narrow terminals.
+pub const SHOTGUN_COMPACT: &str = r#"
+ ,_______________________
+|_______,------._ [__]  ""-,__..---===
+        (_(||||||)_______/  ""        |
+           `------'   [ ))"-,         |
+                       ""   `, _,--.._|
+                              `/     ""
 "#;
 
 pub const SHOTGUN_ART: &str = r#"
@@ -43,199 +62,794 @@ pub const CLICK: &str = r#"
                                                             ""    `,  _,--....___    |
                                                                     `/           """"
 "#;
-pub const SHELL: &str = r#"
-┏━┛┃ ┃┏━┛┃  ┃
-━━┃┏━┃┏━┛┃  ┃
-━━┛┛ ┛━━┛━━┛━━┛
-"#;
 
-fn centered_rect(percent_x: u16, percent_y: u16, area: Rect) -> Rect {
-    let vertical = Layout::default()
-        .direction(Direction::Vertical)
-        .constraints([
-            Constraint::Percentage((100 - percent_y) / 2),
-            Constraint::Percentage(percent_y),
-            Constraint::Percentage((100 - percent_y) / 2),
-        ])
-        .split(area);
-
-    Layout::default()
-        .direction(Direction::Horizontal)
-        .constraints([
-            Constraint::Percentage((100 - percent_x) / 2),
-            Constraint::Percentage(percent_x),
-            Constraint::Percentage((100 - percent_x) / 2),
-        ])
-        .split(vertical[1])[1]
-}
+pub const SHOTGUN_SVG: &str = include_str!("../assets/svg/shotgun.svg");
+pub const SHELL_SVG: &str = include_str!("../assets/svg/shell.svg");
+
+/// The shell drawn next to the chamber strip with `--vector-art`, parsed on first use.
+static SHELL_ART: LazyLock<Option<SvgArt>> = LazyLock::new(|| SvgArt::parse(SHELL_SVG).ok());
 
 pub fn render_ui(app: &App, frame: &mut Frame) -> Option<String> {
-    //will eventually get rid of this
-    let chunks = Layout::default()
-        .direction(Direction::Vertical)
-        // .margin(2)
-        .constraints([
-            Constraint::Percentage(18),
-            Constraint::Percentage(18),
-            Constraint::Percentage(18),
-            Constraint::Percentage(18),
-            Constraint::Percentage(18),
-            Constraint::Min(0),
-        ])
-        .split(frame.area());
+    //nothing is laid out, so the mouse can't hit panels that aren't there
+    let kinds: Vec<WidgetKind> = app.widget_data.iter().map(|(kind, _)| kind).collect();
+    for kind in kinds {
+        app.widget_data.set_area(kind, None);
+    }
+    if app.screen == ScreenSize::TooSmall {
+        render_too_small(frame, frame.area());
+        return None;
+    }
 
-    let border = Block::default()
+    let palette = app.palette();
+    let mut border = Block::default()
         .title("Main UI - Press 'd' for data, 'l' for log")
-        .border_style(Style::default().fg(Color::Red))
+        .border_style(Style::default().fg(palette.border))
         .border_type(BorderType::Rounded)
         .borders(Borders::ALL);
+    if let Some(background) = palette.background {
+        border = border.style(Style::default().bg(background));
+    }
 
+    let inner = shake(border.inner(frame.area()), app.animator.offset());
     frame.render_widget(&border, frame.area());
 
     //this should happen after the round is begun
-    //render inventory
-    //render player
-    //render shotgun
-    render_shotgun_popup(app, frame);
-
-    // does it in order of the "stack"
-    for kind in &app.widget_data.render_stack {
-        let state = app.widget_data.get_state(*kind);
-        if state.display {
-            match kind {
-                WidgetKind::Data => render_data_popup(app, frame),
-                WidgetKind::Log => render_log_popup(app, frame),
-                WidgetKind::Inventory => render_inventory_popup(app, frame, &chunks),
-                WidgetKind::Player => render_player_popup(app, frame),
-                _ => return Some("shotgun is already displayed by default".to_string()),
-            }
-        }
+    //widgets are laid out in the registry's z-order so raising one doesn't move the others,
+    //the stack decides what is drawn over what
+    let stack = app.widget_data.stack();
+    //the title screen takes the place of the table, only dialogs go over it
+    let title = stack.contains(&WidgetKind::Title);
+    let mut specs: Vec<_> = REGISTRY
+        .iter()
+        .filter(|spec| stack.contains(&spec.kind) && (!title || spec.modal))
+        .collect();
+    specs.sort_by_key(|spec| spec.placement.z);
+    let widgets: Vec<(WidgetKind, Placement)> = specs
+        .iter()
+        .map(|spec| {
+            let z = stack.iter().position(|kind| *kind == spec.kind).unwrap_or(0);
+            (spec.kind, Placement { z: z as u8, ..spec.placement_for(app.screen) })
+        })
+        .collect();
+
+    app.widget_data.set_bounds(inner);
+    let placed = LayoutEngine::resolve(inner, &widgets);
+    app.widget_data.set_draw_order(placed.iter().map(|(kind, _)| *kind).collect());
+    for (kind, area) in placed {
+        //panels moved by hand keep their spot, as long as it still fits
+        let area = match app.widget_data.floating(kind) {
+            Some(floating) => window::fit(floating, inner),
+            None => area,
+        };
+        app.widget_data.set_area(kind, Some(area));
+        (registry::spec(kind).render)(app, frame, area);
     }
-    return None
+    None
 }
 
-fn render_data_popup(app: &App, frame: &mut Frame) {
-    let term_area = frame.area();
-    let term_width = term_area.width;
-    let term_height = term_area.height;
-
-    let width = term_width * 30 / 100;
-    let height = term_height * 30 / 100;
-    let area = Rect {
-        x: 1,
-        y: 2,
-        width,
-        height,
-    };
+/// Shown instead of the game while the terminal is below the minimum size.
+fn render_too_small(frame: &mut Frame, area: Rect) {
+    let needed = |have: u16, min: u16| if have < min { Color::Red } else { Color::Green };
+    let text = vec![
+        Line::styled("Terminal too small", Style::default().add_modifier(Modifier::BOLD)),
+        Line::from(vec![
+            Span::raw("now "),
+            Span::styled(area.width.to_string(), Style::default().fg(needed(area.width, screen::MIN_WIDTH))),
+            Span::raw("x"),
+            Span::styled(area.height.to_string(), Style::default().fg(needed(area.height, screen::MIN_HEIGHT))),
+        ]),
+        Line::from(format!("needs {}x{}", screen::MIN_WIDTH, screen::MIN_HEIGHT)),
+        Line::styled("Ctrl-c quits", Style::default().fg(Color::DarkGray)),
+    ];
+    //vertically centered, as far as it fits
+    let top = area.height.saturating_sub(text.len() as u16) / 2;
+    let area = Rect { y: area.y + top, height: area.height - top, ..area };
+    frame.render_widget(Paragraph::new(text).alignment(Alignment::Center).wrap(Wrap { trim: true }), area);
+}
 
-    let popup_content = format!(
-        "Data: {:?} Counter: {}", app.data, app.counter,
-    );
+/// Pushes `area` by `offset` while keeping it on screen, the edge it moves away from shrinks.
+fn shake(area: Rect, offset: (i16, i16)) -> Rect {
+    let (dx, dy) = offset;
+    let dx_abs = dx.unsigned_abs().min(area.width);
+    let dy_abs = dy.unsigned_abs().min(area.height);
+    Rect {
+        x: if dx > 0 { area.x + dx_abs } else { area.x },
+        y: if dy > 0 { area.y + dy_abs } else { area.y },
+        width: area.width - dx_abs,
+        height: area.height - dy_abs,
+    }
+}
 
-    let mut data_popup = Paragraph::new(popup_content)
-        .block(Block::default().title("Popup").borders(Borders::ALL))
-        .wrap(Wrap { trim: true })
-        .style(Style::default()
-            .fg(app.widget_data.get_color(&WidgetKind::Data).unwrap_or(Color::White))
-        );
+pub fn render_data_popup(app: &App, frame: &mut Frame, area: Rect) {
+    let color = app.widget_data.get_color(&WidgetKind::Data).unwrap_or(Color::White);
+    let title = if app.settings.cheats { "Inspector [cheats: +/- edit, J/K move]" } else { "Inspector" };
+    let mut block = Block::default().title(title).borders(Borders::ALL);
     if app.widget_data.is_focused(WidgetKind::Data) {
-        data_popup = data_popup.set_style(Style::default().fg(Color::LightRed));
+        block = block.border_style(Style::default().fg(app.palette().focus));
     }
+    let height = block.inner(area).height as usize;
+
+    let rows = app.inspector.rows(&inspector::tree(app));
+    let scroll = app.inspector.top(rows.len(), height as u16);
+    let lines: Vec<Line> = rows
+        .iter()
+        .enumerate()
+        .skip(scroll)
+        .take(height)
+        .map(|(idx, row)| {
+            let marker = match (row.branch, row.open) {
+                (true, true) => "▾ ",
+                (true, false) => "▸ ",
+                (false, _) => "  ",
+            };
+            let mut style = Style::default().fg(color);
+            if app.settings.cheats && row.edit.is_some() {
+                style = style.fg(Color::Yellow);
+            }
+            if idx == app.inspector.selected {
+                style = style.add_modifier(Modifier::REVERSED);
+            }
+            Line::styled(format!("{}{marker}{}", "  ".repeat(row.depth), row.label), style)
+        })
+        .collect();
 
     frame.render_widget(Clear, area);
-    frame.render_widget(data_popup, area);
+    frame.render_widget(Paragraph::new(lines).block(block), area);
+    //tree rows never wrap, one row is one line
+    render_scrollbar(frame, area, rows.len(), scroll);
 }
 
-fn render_log_popup(app: &App, frame: &mut Frame) {
-    let area = frame.area();
-    let width = (area.width as f32 * 0.33) as u16;
-    let height = (area.height as f32 * 0.75) as u16;
-    let x = (area.width.saturating_sub(width)) / 2;
-    let y = (area.height.saturating_sub(height)) / 2;
-
-    let area = Rect {
-        x,
-        y,
-        width,
-        height,
-    };
-
-    let log_content = app.log.iter().map(|s| s.as_str()).collect::<Vec<_>>().join("\n");
-    let mut log_popup = Paragraph::new(log_content)
-        .block(Block::default().title("Message Log - use j k to navigate").borders(Borders::ALL))
-        .wrap(Wrap {trim: true})
-        .scroll((app.log_scroll, 0));
+pub fn render_log_popup(app: &App, frame: &mut Frame, area: Rect) {
+    let view = &app.log_view;
+    let mut title = String::from("Message Log - j k scroll, / search");
+    if view.follow {
+        title.push_str(" [follow]");
+    }
+    let mut block = Block::default().title(title).borders(Borders::ALL);
+    if view.searching || !view.query.is_empty() {
+        let cursor = if view.searching { "_" } else { "" };
+        block = block.title_bottom(format!("/{}{cursor}", view.query));
+    }
     if app.widget_data.is_focused(WidgetKind::Log) {
-        log_popup = log_popup.set_style(Style::default().fg(Color::LightRed));
+        block = block.border_style(Style::default().fg(app.palette().focus));
     }
+    let inner = block.inner(area);
+
+    let lines = LogView::lines(&app.log);
+    let scroll = view.scroll(&lines, inner.width, inner.height);
+    let content_height = log_view::content_height(&lines, inner.width);
+    let text: Vec<Line> = lines
+        .iter()
+        .map(|line| {
+            if line.divider {
+                Line::styled(line.text.as_str(), Style::default().fg(app.palette().muted)).centered()
+            } else {
+                highlight(&line.text, view)
+            }
+        })
+        .collect();
+
+    let log_popup = Paragraph::new(text)
+        .block(block)
+        .wrap(Wrap { trim: false })
+        .scroll((scroll as u16, 0));
 
     frame.render_widget(Clear, area);
     frame.render_widget(log_popup, area);
+
+    render_scrollbar(frame, area, content_height, scroll);
 }
 
+/// Draws a scrollbar over the right border of a panel, only when there is something to scroll.
+fn render_scrollbar(frame: &mut Frame, area: Rect, content_height: usize, scroll: usize) {
+    let height = area.height.saturating_sub(2);
+    let max = text::max_scroll(content_height, height);
+    if max == 0 {
+        return;
+    }
+    let mut scrollbar = ScrollbarState::new(content_height)
+        .position(scroll)
+        .viewport_content_length(height as usize);
+    frame.render_stateful_widget(
+        Scrollbar::new(ScrollbarOrientation::VerticalRight),
+        area.inner(Margin { vertical: 1, horizontal: 0 }),
+        &mut scrollbar,
+    );
+}
+
+/// Splits a log line so every match of the search is highlighted.
+fn highlight<'a>(text: &'a str, view: &LogView) -> Line<'a> {
+    if !view.matches(text) {
+        return Line::from(text);
+    }
+    let lower = text.to_lowercase();
+    let query = view.query.to_lowercase();
+    //lowercasing can change byte lengths, only highlight when the offsets still line up
+    if lower.len() != text.len() {
+        return Line::from(text);
+    }
+
+    let mut spans = Vec::new();
+    let mut last = 0;
+    for (start, _) in lower.match_indices(&query) {
+        spans.push(Span::raw(&text[last..start]));
+        spans.push(Span::styled(
+            &text[start..start + query.len()],
+            Style::default().fg(Color::Black).bg(Color::Yellow),
+        ));
+        last = start + query.len();
+    }
+    spans.push(Span::raw(&text[last..]));
+    Line::from(spans)
+}
 
-fn render_inventory_popup(app: &App, frame: &mut Frame, chunks: &[Rect]) {
-    let area = chunks[2];
-    let log_content = app.log.iter().map(|s| s.as_str()).collect::<Vec<_>>().join("\n");
-    let mut log_popup = Paragraph::new(log_content)
-        .block(Block::default().title("Message Log").borders(Borders::ALL))
-        .wrap(Wrap {trim: true})
-        .scroll((app.log_scroll, 0));
+pub fn render_inventory_popup(app: &App, frame: &mut Frame, area: Rect) {
+    let mut title = match app.active_player() {
+        Some(player) => format!("Inventory - {}", player.name),
+        None => String::from("Inventory"),
+    };
+    //the clock panel may be closed, the grace window shows here too
+    if app.turn_timer.in_grace() {
+        title.push_str(&format!(" - hold {}s", app.turn_timer.grace_seconds()));
+    }
+    let mut block = Block::default().title(title).borders(Borders::ALL);
     if app.widget_data.is_focused(WidgetKind::Inventory) {
-        log_popup = log_popup.set_style(Style::default().fg(Color::LightRed))
+        block = block.border_style(Style::default().fg(app.palette().focus));
     }
+    let (slots_area, description_area) = inventory_layout(block.inner(area));
+
+    let description = match app.active_player() {
+        None => Line::from("Load the shotgun to start the match."),
+        Some(player) => match player.slots().get(app.inventory_selected) {
+            None => Line::from("No items."),
+            Some((item, _)) => {
+                let mut line = Line::from(vec![
+                    Span::styled(item.name(), Style::default().add_modifier(Modifier::BOLD)),
+                    Span::raw(": "),
+                    Span::raw(item.description()),
+                ]);
+                if !item.usable(app.match_data.phase(), player, &app.data.shotgun) {
+                    line.push_span(Span::styled(" (can't use now)", Style::default().fg(app.palette().muted)));
+                }
+                line
+            },
+        },
+    };
 
     frame.render_widget(Clear, area);
-    frame.render_widget(log_popup, chunks[2]);
+    frame.render_widget(block, area);
+    frame.render_widget(inventory_grid(app), slots_area);
+    frame.render_widget(Paragraph::new(description).wrap(Wrap { trim: true }), description_area);
 }
 
-fn render_player_popup(app: &App, frame: &mut Frame) {
+/// Slots on top, the description of the selected item below them.
+pub fn inventory_layout(area: Rect) -> (Rect, Rect) {
+    let [slots, description] = Layout::vertical([Constraint::Fill(1), Constraint::Length(2)]).areas(area);
+    (slots, description)
+}
 
-    let area = Rect {
-        x: 10,
-        y: 5,
-        width: 10,
-        height: 10,
-    };
+/// One slot per item kind the active player holds, items that can't be used are greyed out.
+pub fn inventory_grid(app: &App) -> Grid<'_> {
+    let pack = app.pack();
+    let mut cells: Vec<Text> = Vec::new();
+    if let Some(player) = app.active_player() {
+        for (idx, (item, count)) in player.slots().iter().enumerate() {
+            let mut cell = Text::from(vec![
+                Line::from(format!("{} {} x{}", idx + 1, pack.item_icon(item), count)),
+                Line::from(item.name()),
+            ]);
+            if !item.usable(app.match_data.phase(), player, &app.data.shotgun) {
+                cell = cell.style(Style::default().fg(app.palette().muted));
+            }
+            cells.push(cell);
+        }
+    }
+    //empty slots keep their number so the keys still line up
+    while cells.len() < MAX_ITEMS {
+        let number = cells.len() + 1;
+        cells.push(Text::styled(format!("{number} -"), Style::default().fg(app.palette().muted)));
+    }
 
-    // The "icon" — can be emoji, unicode, ASCII art, etc.
+    let selected = app.active_player().and_then(|player| {
+        (app.inventory_selected < player.slots().len()).then_some(app.inventory_selected)
+    });
+    Grid::new(MAX_ITEMS, 1)
+        .cells(cells)
+        .spacing(1)
+        .selected(selected)
+        .highlight_style(Style::default().add_modifier(Modifier::REVERSED))
+}
 
-    let mut player_popup = Paragraph::new(PLAYER_ART)
-        .block(Block::default().title("Popup").borders(Borders::NONE));
-    if app.widget_data.is_focused(WidgetKind::Data) {
-        player_popup = player_popup.set_style(Style::default().fg(Color::LightRed))
+pub fn render_player_popup(app: &App, frame: &mut Frame, area: Rect) {
+    frame.render_widget(Clear, area);
+    frame.render_widget(player_grid(app), area);
+}
+
+/// One cell per player, the one whose turn it is is highlighted and the one they aim at marked.
+pub fn player_grid(app: &App) -> Grid<'_> {
+    let title = match app.data.rules.rounds {
+        Rounds::Endless => format!("Players - pot {} x{}", app.match_data.pot(), app.match_data.multiplier()),
+        Rounds::Match => String::from("Players"),
+    };
+    let mut block = Block::default().title(title).borders(Borders::ALL);
+    //a damage flash wins over the focus color
+    if app.animator.is_animating(WidgetKind::Player)
+        && let Some(color) = app.widget_data.get_color(&WidgetKind::Player)
+    {
+        block = block.border_style(Style::default().fg(color));
+    } else if app.widget_data.is_focused(WidgetKind::Player) {
+        block = block.border_style(Style::default().fg(app.palette().focus));
+    }
+    let turn = app.match_data.turn();
+    let target = turn.and_then(|shooter| app.target_of(shooter));
+
+    let mut titles: Vec<Line> = Vec::new();
+    let mut cells: Vec<Text> = Vec::new();
+    for (idx, player) in app.data.players.iter().enumerate() {
+        let marker = if turn == Some(idx) {
+            "▶ "
+        } else if target == Some(idx) {
+            "◎ "
+        } else {
+            ""
+        };
+        titles.push(Line::from(format!("{marker}{}", player.name)));
+
+        let pips = "♥".repeat(player.health as usize) + &"♡".repeat(player.max_health.saturating_sub(player.health) as usize);
+        let statuses = match player.statuses.as_slice() {
+            [] => String::from("-"),
+            statuses => statuses.iter().map(|status| status.name()).collect::<Vec<_>>().join(", "),
+        };
+        let mut cell = Text::from(vec![
+            Line::styled(pips, Style::default().fg(Color::Red)),
+            Line::from(format!("items: {}", player.items.len())),
+            Line::styled(statuses, Style::default().fg(Color::Yellow)),
+        ]);
+        if !player.is_alive() {
+            cell = cell.style(Style::default().fg(app.palette().muted));
+        }
+        cells.push(cell);
     }
 
-    frame.render_widget(Clear, area);
-    frame.render_widget(player_popup, area);
+    //stacked on the left normally, side by side along the top when compact
+    let count = app.data.players.len();
+    let (cols, rows) = if app.screen == ScreenSize::Compact { (count, 1) } else { (1, count) };
+    Grid::new(cols, rows)
+        .cells(cells)
+        .titles(titles)
+        .borders(true)
+        .wrap(true)
+        .selected(turn)
+        .highlight_style(Style::default().fg(app.palette().focus))
+        .block(block)
 }
 
 //begin changing "popups" to not be such as shotgun and inventory
-fn render_shotgun_popup(app: &App, frame: &mut Frame) {
-    let frame_area = frame.area();
-    let w = 68;
-    let h = 10;
-    let w = w.min(frame_area.width);
-    let h = h.min(frame_area.height);
-
-    let x = frame_area.x + (frame_area.width - w) / 2;
-    let y = frame_area.y + (frame_area.height - h) / 2;
+pub fn render_shotgun_popup(app: &App, frame: &mut Frame, area: Rect) {
+    let [area, chamber] = Layout::vertical([Constraint::Fill(1), Constraint::Length(2)]).areas(area);
+    frame.render_widget(Clear, chamber);
+    //the vector shell sits left of the strip, an empty column on the right keeps the strip centered
+    let chamber = if app.settings.vector_art
+        && let Some(shell_art) = SHELL_ART.as_ref()
+    {
+        let [shell, strip, _] = Layout::horizontal([Constraint::Length(3), Constraint::Fill(1), Constraint::Length(3)]).areas(chamber);
+        frame.render_widget(shell_art.widget().marker(Marker::HalfBlock).color(next_shell_color(app)), shell);
+        strip
+    } else {
+        chamber
+    };
+    frame.render_widget(chamber_strip(app), chamber);
+
+    //animations are ascii frames, so the vector art only shows while the shotgun is idle
+    if app.settings.vector_art
+        && !app.animator.is_animating(WidgetKind::Shotgun)
+        && let Some(art) = &app.pack().shotgun_svg
+    {
+        let focused = app.widget_data.is_focused(WidgetKind::Shotgun).then_some(app.palette().focus);
+        frame.render_widget(Clear, area);
+        frame.render_widget(art.widget().color(focused), area);
+        return;
+    }
 
-    let area = Rect { x, y, width: w, height: h };
-    let mut shotgun_popup = Paragraph::new(SHOTGUN_ART)
-        .block(Block::default().borders(Borders::empty()));
+    let mut art = Text::from(app.widget_data.content(WidgetKind::Shotgun).unwrap_or(app.pack().shotgun.clone()));
+    //idle art that doesn't fit gets swapped for the short one, animation frames are cut off
+    //on the right so the BANG stays readable
+    if art.width() as u16 > area.width && !app.animator.is_animating(WidgetKind::Shotgun) {
+        art = Text::from(SHOTGUN_COMPACT);
+    }
+    //keep the art centered no matter how wide the current frame is
+    let width = (art.width() as u16).min(area.width);
+    let area = Rect { x: area.x + (area.width - width) / 2, width, ..area };
+    let mut shotgun_popup = Paragraph::new(art)
+        .block(Block::default().borders(Borders::empty()))
+        .style(Style::default()
+            .fg(app.widget_data.get_color(&WidgetKind::Shotgun).unwrap_or(Color::White))
+        );
 
     if app.widget_data.is_focused(WidgetKind::Shotgun) {
-        shotgun_popup = shotgun_popup.set_style(Style::default().fg(Color::LightRed))
+        shotgun_popup = shotgun_popup.set_style(Style::default().fg(app.palette().focus))
     }
 
     frame.render_widget(Clear, area);
     frame.render_widget(shotgun_popup, area);
 }
 
-fn render_confirm_popup(app: &App, frame: &mut Frame) {
+/// Shells of the round in firing order: fired ones are shown to everyone, the rest only if the
+/// player whose turn it is has seen them and isn't the computer.
+fn chamber_strip(app: &App) -> Paragraph<'_> {
+    let pack = app.pack();
+    let shotgun = &app.data.shotgun;
+    let viewer = app.viewer();
+    let fired = shotgun.fired.borrow();
+    let loaded = shotgun.loaded.borrow().len();
+
+    let mut shells: Vec<Span> = Vec::new();
+    for position in 0..loaded {
+        let span = match (fired.get(position), viewer.and_then(|player| player.knows(position))) {
+            (Some(shell), _) => Span::styled(pack.shell_icon(shell).to_string(), Style::default().fg(app.palette().muted)),
+            (None, Some(shell)) => Span::styled(pack.shell_icon(shell).to_string(), Style::default().fg(shell_color(shell))),
+            (None, None) => Span::raw("?"),
+        };
+        shells.push(span);
+        shells.push(Span::raw(" "));
+    }
+
+    let (live, blank, special) = shotgun.tally();
+    let mut tally = format!("{} left | loaded {} live, {} blank", shotgun.shell_count(), live, blank);
+    if special > 0 {
+        tally.push_str(&format!(", {} special", special));
+    }
+    Paragraph::new(vec![Line::from(shells), Line::styled(tally, Style::default().fg(Color::Gray))])
+        .alignment(Alignment::Center)
+}
+
+/// Color of the shell in the chamber as the viewer knows it, `None` keeps the art's own.
+fn next_shell_color(app: &App) -> Option<Color> {
+    let shotgun = &app.data.shotgun;
+    if shotgun.shell_count() == 0 {
+        return Some(app.palette().muted);
+    }
+    app.viewer()
+        .and_then(|player| player.knows(shotgun.position()))
+        .map(shell_color)
+}
+
+/// What color a shell is drawn in once it is known.
+pub fn shell_color(shell: &Shell) -> Color {
+    match shell {
+        Shell::Live | Shell::Imposter | Shell::SelfDestruct => Color::Red,
+        Shell::Blank => Color::Blue,
+        Shell::Poison => Color::Green,
+        Shell::BeanBag => Color::Magenta,
+        Shell::Taser => Color::LightCyan,
+    }
+}
+
+/// The turn clock, a bar that empties as the turn runs out.
+pub fn render_clock_popup(app: &App, frame: &mut Frame, area: Rect) {
+    let timer = &app.turn_timer;
+    let block = Block::default().title("Turn").borders(Borders::ALL);
+    let (label, ratio, color) = if timer.in_grace() {
+        (format!("hold {}s", timer.grace_seconds()), 1.0, Color::Yellow)
+    } else if !timer.is_running() {
+        (String::from("--"), 0.0, app.palette().muted)
+    } else {
+        let ratio = timer.ratio();
+        let color = if ratio < 0.25 { Color::Red } else { app.widget_data.get_color(&WidgetKind::Clock).unwrap_or(Color::White) };
+        (format!("{}s", timer.seconds_left()), ratio, color)
+    };
+
+    let gauge = LineGauge::default()
+        .block(block)
+        .label(label)
+        .ratio(ratio)
+        .filled_style(Style::default().fg(color))
+        .unfilled_style(Style::default().fg(app.palette().muted));
+    frame.render_widget(Clear, area);
+    frame.render_widget(gauge, area);
+}
+
+pub fn render_confirm_popup(app: &App, frame: &mut Frame, area: Rect) {
+    let Some(confirm) = app.confirm else {
+        return;
+    };
     //three rects/ one big one and two small ones
-    let frame_area = frame.area();
-    let w = frame_area.width;
-    let h = frame_area.height;
+    let (message, yes, no) = Confirm::layout(area);
+    let color = app.widget_data.get_color(&WidgetKind::Confirm).unwrap_or(Color::White);
+
+    frame.render_widget(Clear, area);
+    frame.render_widget(
+        Block::default()
+            .title(confirm.action.title())
+            .borders(Borders::ALL)
+            .border_type(BorderType::Rounded)
+            .border_style(Style::default().fg(color)),
+        area,
+    );
+    frame.render_widget(
+        Paragraph::new(confirm.action.message())
+            .alignment(Alignment::Center)
+            .wrap(Wrap { trim: true }),
+        message,
+    );
+
+    for (label, button, selected) in [
+        (confirm.action.yes_label(), yes, confirm.yes_selected),
+        (confirm.action.no_label(), no, !confirm.yes_selected),
+    ] {
+        let style = if selected {
+            Style::default().fg(Color::Black).bg(app.palette().focus)
+        } else {
+            Style::default()
+        };
+        frame.render_widget(
+            Paragraph::new(label)
+                .alignment(Alignment::Center)
+                .style(style)
+                .block(Block::default().borders(Borders::ALL).border_style(Style::default().fg(color))),
+            button,
+        );
+    }
+}
+
+pub fn render_pause_popup(app: &App, frame: &mut Frame, area: Rect) {
+    let Some(menu) = &app.pause else {
+        return;
+    };
+    let palette = app.palette();
+    let title = match menu.page {
+        Menu::Pause => "Paused",
+        Menu::Settings => "Settings",
+        Menu::Rules => "Rules",
+    };
+    let hint = match (menu.page, menu.binding) {
+        (_, Some(_)) => "any key binds it, Esc cancels",
+        (Menu::Pause, None) => "Esc resumes, Ctrl-c quits the game",
+        (Menu::Settings, None) => "Left/Right change, Enter rebinds, Esc back",
+        (Menu::Rules, None) => "j k scroll, Esc back",
+    };
+    let block = Block::default()
+        .title(title)
+        .title_bottom(Line::styled(hint, Style::default().fg(palette.muted)).centered())
+        .borders(Borders::ALL)
+        .border_type(BorderType::Rounded)
+        .border_style(Style::default().fg(palette.focus));
+    frame.render_widget(Clear, area);
+    frame.render_widget(block, area);
+
+    let list = menu.list(area);
+    let style = |row: usize| {
+        if row == menu.selected {
+            Style::default().fg(Color::Black).bg(palette.focus)
+        } else {
+            Style::default()
+        }
+    };
+    let keymap = &app.settings.preferences.keymap;
+    let lines: Vec<Line> = match menu.page {
+        Menu::Pause => PauseItem::ALL
+            .iter()
+            .enumerate()
+            .map(|(row, item)| Line::styled(item.label(), style(row)).centered())
+            .collect(),
+        Menu::Settings => SettingsRow::all()
+            .into_iter()
+            .enumerate()
+            .map(|(row, setting)| {
+                let preferences = &app.settings.preferences;
+                let (label, value) = match setting {
+                    SettingsRow::Theme => ("Theme", format!("< {} >", preferences.theme.name())),
+                    SettingsRow::AnimationSpeed => ("Animation speed", format!("< {}x >", preferences.animation_speed)),
+                    SettingsRow::TickRate => ("Game ticks", format!("< {}/s >", app.settings.tick_fps())),
+                    SettingsRow::FrameRate => ("Frame rate", format!("< {}/s >", app.settings.frame_fps())),
+                    SettingsRow::Key(action) if menu.binding == Some(action) => (action.name(), String::from("press a key")),
+                    SettingsRow::Key(action) => (action.name(), format!("[{}]", Keymap::label(keymap.key(action)))),
+                    SettingsRow::Back => return Line::styled("Back", style(row)).centered(),
+                };
+                list.labelled(label, &value, style(row))
+            })
+            .collect(),
+        Menu::Rules => {
+            let area = list.area;
+            let scroll = menu.scroll.min(text::max_scroll(text::wrapped_height(pause::RULES, area.width), area.height));
+            frame.render_widget(Paragraph::new(pause::RULES).wrap(Wrap { trim: false }).scroll((scroll as u16, 0)), area);
+            Vec::new()
+        },
+    };
+    list.render(frame, lines);
+
+    if let Some(notice) = &menu.notice {
+        let line = Rect { y: list.area.bottom(), height: 1, ..list.area };
+        frame.render_widget(Line::styled(notice.as_str(), Style::default().fg(Color::Yellow)).centered(), line);
+    }
+}
+
+pub fn render_title_popup(app: &App, frame: &mut Frame, area: Rect) {
+    let Some(title) = &app.title else {
+        return;
+    };
+    let palette = app.palette();
+    let hint = match (title.page, title.editing) {
+        (_, true) => "type away, Enter when done",
+        (TitlePage::Main, false) => "Enter picks, Ctrl-c quits",
+        (TitlePage::NewGame, false) => "Left/Right change, Enter types a name or seed, Esc back",
+        (TitlePage::PoolEditor, false) => "Left/Right change, Enter next number, Esc back",
+        (TitlePage::Replays, false) => "Enter types the seed of a match, Esc back",
+    };
+    let block = Block::default()
+        .title_bottom(Line::styled(hint, Style::default().fg(palette.muted)).centered())
+        .borders(Borders::ALL)
+        .border_type(BorderType::Rounded)
+        .border_style(Style::default().fg(palette.focus));
+    frame.render_widget(Clear, area);
+    frame.render_widget(block, area);
+
+    let (banner, _, notice) = TitleMenu::layout(area);
+    let list = title.list(area);
+    let heading = match title.page {
+        TitlePage::Main => "shotgun roulette reinvented for the terminal",
+        TitlePage::NewGame => "New Game",
+        TitlePage::PoolEditor => "Shell Pools",
+        TitlePage::Replays => "Replays",
+    };
+    frame.render_widget(
+        Paragraph::new(vec![
+            Line::styled("C L I   R O U L E T T E", Style::default().fg(palette.border).add_modifier(Modifier::BOLD)),
+            Line::styled(heading, Style::default().fg(palette.muted)),
+        ])
+        .centered(),
+        banner,
+    );
+
+    let style = |row: usize| {
+        if row == title.selected {
+            Style::default().fg(Color::Black).bg(palette.focus)
+        } else {
+            Style::default()
+        }
+    };
+    let lines: Vec<Line> = match title.page {
+        TitlePage::Main => TitleItem::ALL
+            .iter()
+            .enumerate()
+            .map(|(row, item)| {
+                let line = Line::styled(item.label(), style(row)).centered();
+                //there is nothing to continue
+                if *item == TitleItem::Continue && !title.has_save && row != title.selected {
+                    line.style(Style::default().fg(palette.muted))
+                } else {
+                    line
+                }
+            })
+            .collect(),
+        TitlePage::NewGame => {
+            let setup = &title.setup;
+            //only custom games pick these, the other modes show what they bring
+            let custom = setup.mode == CUSTOM;
+            let rules = app.catalog.rules(setup);
+            let value = |text: String| if custom { format!("< {text} >") } else { text };
+            SetupRow::all(setup)
+                .into_iter()
+                .enumerate()
+                .map(|(row, setup_row)| {
+                    let editing = title.editing && row == title.selected;
+                    let (label, value) = match setup_row {
+                        SetupRow::Mode => (String::from("Mode"), format!("< {} >", app.catalog.mode_name(&setup.mode))),
+                        SetupRow::Players => (String::from("Players"), format!("< {} >", setup.seats.len())),
+                        SetupRow::Seat(idx) => {
+                            let seat = &setup.seats[idx];
+                            let name = if editing { format!("{}_", seat.name) } else { seat.name.clone() };
+                            (name, format!("< {} >", if seat.ai { "Computer" } else { "Human" }))
+                        },
+                        SetupRow::Health => (String::from("Starting health"), value(rules.health.to_string())),
+                        SetupRow::Shells => (String::from("Shells"), value(rules.pool.name.clone())),
+                        SetupRow::Items => (String::from("Items"), value(rules.items.name().to_string())),
+                        SetupRow::Seed if editing => {
+                            let seed = setup.seed.map_or(String::new(), |seed| seed.to_string());
+                            (String::from("Seed"), format!("[{seed}_]"))
+                        },
+                        SetupRow::Seed => {
+                            let seed = setup.seed.map_or(String::from("random"), |seed| seed.to_string());
+                            (String::from("Seed"), format!("[{seed}]"))
+                        },
+                        SetupRow::Start => return Line::styled("Start", style(row)).centered(),
+                        SetupRow::Back => return Line::styled("Back", style(row)).centered(),
+                    };
+                    list.labelled(&label, &value, style(row))
+                })
+                .collect()
+        },
+        TitlePage::PoolEditor => {
+            let editor = &title.editor;
+            PoolRow::all()
+                .into_iter()
+                .enumerate()
+                .map(|(row, pool_row)| {
+                    let (label, value) = match &pool_row {
+                        PoolRow::Load => {
+                            let base = editor.base.and_then(|base| app.catalog.pools.get(base));
+                            (String::from("Edit"), format!("< {} >", base.map_or("new pool", |pool| pool.name.as_str())))
+                        },
+                        PoolRow::Name if title.editing => (String::from("Name"), format!("[{}_]", editor.pool.name)),
+                        PoolRow::Name => (String::from("Name"), format!("[{}]", editor.pool.name)),
+                        PoolRow::GuaranteedBlank => {
+                            (String::from("Guaranteed blank"), format!("< {} >", if editor.pool.guaranteed_blank { "On" } else { "Off" }))
+                        },
+                        PoolRow::Shell(shell) => {
+                            let rule = editor.rule(shell);
+                            let max = rule.max.map_or(String::from("-"), |max| max.to_string());
+                            //the number Left/Right change is in brackets
+                            let numbers = [rule.weight.to_string(), rule.min.to_string(), max];
+                            let value = ShellField::ALL
+                                .iter()
+                                .zip(numbers)
+                                .map(|(field, number)| {
+                                    if row == title.selected && *field == editor.field {
+                                        format!("{} <{number:>2}>", field.label())
+                                    } else {
+                                        format!("{}  {number:>2} ", field.label())
+                                    }
+                                })
+                                .collect::<String>();
+                            (format!("{} {}", app.pack().shell_icon(shell), shell.key()), value)
+                        },
+                        PoolRow::Save => return Line::styled("Save", style(row)).centered(),
+                        PoolRow::Back => return Line::styled("Back", style(row)).centered(),
+                    };
+                    list.labelled(&label, &value, style(row))
+                })
+                .collect()
+        },
+        TitlePage::Replays => ReplayRow::ALL
+            .into_iter()
+            .enumerate()
+            .map(|(row, replay_row)| {
+                let value = match replay_row {
+                    ReplayRow::Seed if title.editing => format!("[{}_]", title.replay_seed.map_or(String::new(), |seed| seed.to_string())),
+                    ReplayRow::Seed => format!("[{}]", title.replay_seed.map_or(String::from("none"), |seed| seed.to_string())),
+                    ReplayRow::Play => return Line::styled("Play again", style(row)).centered(),
+                    ReplayRow::Back => return Line::styled("Back", style(row)).centered(),
+                };
+                list.labelled("Seed", &value, style(row))
+            })
+            .collect(),
+    };
+    list.render(frame, lines);
+
+    if let Some(text) = &title.notice {
+        frame.render_widget(Line::styled(text.as_str(), Style::default().fg(Color::Yellow)).centered(), notice);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// The shell row of the chamber strip as text.
+    fn strip_row(app: &App) -> String {
+        let area = Rect::new(0, 0, 20, 2);
+        let mut buf = Buffer::empty(area);
+        chamber_strip(app).render(area, &mut buf);
+        (0..area.width).map(|x| buf[(x, 0)].symbol()).collect::<String>().trim().to_string()
+    }
+
+    #[tokio::test]
+    async fn what_the_computer_saw_stays_off_the_strip() {
+        let mut app = App::new();
+        let pool = app.data.rules.pool.clone();
+        app.data.shotgun.load(&mut app.rng, &pool, 3);
+        app.match_data.set_turn(Some(1));
+        let dealer = &mut app.data.players[1];
+        assert!(dealer.ai);
+        for position in 0..3 {
+            dealer.reveal(position, Shell::Live);
+        }
+        assert_eq!(strip_row(&app), "? ? ?");
+        assert_eq!(next_shell_color(&app), None);
+
+        //the same knowledge on a human's turn is shown
+        app.data.players[1].ai = false;
+        assert_eq!(strip_row(&app), "● ● ●");
+        assert_eq!(next_shell_color(&app), Some(Color::Red));
+    }
 }
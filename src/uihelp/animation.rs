@@ -0,0 +1,252 @@
+//animation.rs
+use std::time::Duration;
+
+use ratatui::style::Color;
+
+use crate::event::TICK_FPS;
+use crate::uihelp::widget_data::{WidgetData, WidgetKind};
+
+/// Shapes how the progress of an animation moves from 0 to 1.
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub enum Easing {
+    #[default]
+    Linear,
+    EaseIn,
+    EaseOut,
+    EaseInOut,
+}
+
+impl Easing {
+    pub fn apply(&self, t: f32) -> f32 {
+        let t = t.clamp(0.0, 1.0);
+        match self {
+            Easing::Linear => t,
+            Easing::EaseIn => t * t,
+            Easing::EaseOut => t * (2.0 - t),
+            Easing::EaseInOut => {
+                if t < 0.5 {
+                    2.0 * t * t
+                } else {
+                    -1.0 + (4.0 - 2.0 * t) * t
+                }
+            },
+        }
+    }
+}
+
+/// One step of an animation, `content` and `color` replace the widget's own while it is showing.
+#[derive(Debug, Clone)]
+pub struct AnimFrame {
+    pub content: Option<String>,
+    pub color: Option<Color>,
+    /// how far the screen is pushed while the frame shows, for shakes and recoil
+    pub offset: (i16, i16),
+    pub duration: Duration,
+    //counted out when the animation starts playing, at the tick rate and speed of the moment
+    ticks: u32,
+}
+
+impl AnimFrame {
+    pub fn new(content: impl Into<String>, duration: Duration) -> AnimFrame {
+        AnimFrame { content: Some(content.into()), color: None, offset: (0, 0), duration, ticks: 0 }
+    }
+
+    /// A frame that only recolors the widget, e.g. a damage flash.
+    pub fn flash(color: Color, duration: Duration) -> AnimFrame {
+        AnimFrame { content: None, color: Some(color), offset: (0, 0), duration, ticks: 0 }
+    }
+
+    pub fn color(mut self, color: Color) -> Self {
+        self.color = Some(color);
+        self
+    }
+
+    pub fn offset(mut self, x: i16, y: i16) -> Self {
+        self.offset = (x, y);
+        self
+    }
+}
+
+/// A sequence of content swaps on a single widget, driven by [`crate::event::Event::Tick`].
+#[derive(Debug, Clone)]
+pub struct Animation {
+    pub target: WidgetKind,
+    frames: Vec<AnimFrame>,
+    easing: Easing,
+    //which frame is showing and how many ticks the animation has been playing
+    current: usize,
+    elapsed: u32,
+    //what the widget showed before the animation started
+    restore: Option<(Option<String>, Option<Color>)>,
+}
+
+impl Animation {
+    pub fn new(target: WidgetKind, frames: Vec<AnimFrame>) -> Animation {
+        Animation {
+            target,
+            frames,
+            easing: Easing::default(),
+            current: 0,
+            elapsed: 0,
+            restore: None,
+        }
+    }
+
+    /// Shows `content` for `duration` and then puts the old content back.
+    pub fn transient(target: WidgetKind, content: impl Into<String>, duration: Duration) -> Animation {
+        Animation::new(target, vec![AnimFrame::new(content, duration)])
+    }
+
+    /// Bends the time the frames take, e.g. with `EaseOut` the first frames rush by and the last
+    /// ones linger. The animation as a whole still takes as long.
+    pub fn easing(mut self, easing: Easing) -> Self {
+        self.easing = easing;
+        self
+    }
+
+    fn total_ticks(&self) -> u32 {
+        self.frames.iter().map(|frame| frame.ticks).sum()
+    }
+
+    /// How far along the whole animation is, from 0 to 1 after easing.
+    pub fn progress(&self) -> f32 {
+        let total = self.total_ticks();
+        if total == 0 {
+            return 1.0;
+        }
+        self.easing.apply(self.elapsed as f32 / total as f32)
+    }
+
+    pub fn is_finished(&self) -> bool {
+        self.current >= self.frames.len()
+    }
+
+    pub fn current_frame(&self) -> Option<&AnimFrame> {
+        self.frames.get(self.current)
+    }
+
+    /// Advances one tick, returns whether a new frame started.
+    ///
+    /// The frame showing is the one the eased progress falls in, so easing changes when the frames
+    /// and their offsets come up.
+    fn step(&mut self) -> bool {
+        let before = self.current;
+        self.elapsed += 1;
+        let total = self.total_ticks();
+        let at = self.progress() * total as f32;
+        let mut end = 0;
+        self.current = if self.elapsed < total {
+            self.frames
+                .iter()
+                .position(|frame| {
+                    end += frame.ticks;
+                    at < end as f32
+                })
+                .unwrap_or(self.frames.len())
+        } else {
+            self.frames.len()
+        };
+        self.current != before
+    }
+}
+
+/// Owns every running [`Animation`] and writes their frames into [`WidgetData`].
+///
+/// Only one animation runs per widget, starting a new one cancels the old one.
+#[derive(Debug)]
+pub struct Animator {
+    animations: Vec<Animation>,
+    /// how fast animations play, 2 is twice as fast
+    pub speed: f32,
+    /// game ticks per second, what frame durations are counted out in
+    pub tick_fps: f64,
+}
+
+impl Default for Animator {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Animator {
+    pub fn new() -> Animator {
+        Animator { animations: Vec::new(), speed: 1.0, tick_fps: TICK_FPS }
+    }
+
+    pub fn play(&mut self, widget_data: &WidgetData, mut animation: Animation) {
+        let target = animation.target;
+        let restore = match self.animations.iter().position(|a| a.target == target) {
+            //keep what was there before the first animation, not the old animation's frame
+            Some(idx) => self.animations.remove(idx).restore,
+            None => None,
+        };
+        let restore = restore.unwrap_or_else(|| (widget_data.content(target), widget_data.get_color(&target)));
+
+        let speed = if self.speed > 0.0 { self.speed } else { 1.0 };
+        for frame in animation.frames.iter_mut() {
+            frame.ticks = ticks_for(frame.duration.div_f32(speed), self.tick_fps);
+        }
+        let Some(frame) = animation.frames.first() else {
+            return;
+        };
+        Self::show(widget_data, target, frame, &restore);
+        animation.restore = Some(restore);
+        self.animations.push(animation);
+    }
+
+    /// Moves every animation forward by one tick and restores widgets whose animation ended.
+    ///
+    /// Returns whether any widget changed, frames that are held for several ticks only ask for a
+    /// redraw on the tick they start.
+    pub fn tick(&mut self, widget_data: &WidgetData) -> bool {
+        let mut changed = false;
+        self.animations.retain_mut(|animation| {
+            let target = animation.target;
+            let restore = animation.restore.clone().unwrap_or_default();
+            let started = animation.step();
+            match animation.current_frame() {
+                Some(frame) => {
+                    if started {
+                        Self::show(widget_data, target, frame, &restore);
+                        changed = true;
+                    }
+                    true
+                },
+                None => {
+                    widget_data.change_content(target, restore.0);
+                    widget_data.set_color(target, restore.1);
+                    changed = true;
+                    false
+                },
+            }
+        });
+        changed
+    }
+
+    /// Frames without content or color fall back to what the widget had before.
+    fn show(widget_data: &WidgetData, target: WidgetKind, frame: &AnimFrame, restore: &(Option<String>, Option<Color>)) {
+        widget_data.change_content(target, frame.content.clone().or_else(|| restore.0.clone()));
+        widget_data.set_color(target, frame.color.or(restore.1));
+    }
+
+    pub fn is_animating(&self, kind: WidgetKind) -> bool {
+        self.animations.iter().any(|animation| animation.target == kind)
+    }
+
+    pub fn is_idle(&self) -> bool {
+        self.animations.is_empty()
+    }
+
+    /// Sum of the offsets of every frame that is showing right now.
+    pub fn offset(&self) -> (i16, i16) {
+        self.animations
+            .iter()
+            .filter_map(Animation::current_frame)
+            .fold((0, 0), |(x, y), frame| (x + frame.offset.0, y + frame.offset.1))
+    }
+}
+
+/// How many ticks at `tick_fps` make up `duration`, never less than one.
+pub fn ticks_for(duration: Duration, tick_fps: f64) -> u32 {
+    ((duration.as_secs_f64() * tick_fps).round() as u32).max(1)
+}
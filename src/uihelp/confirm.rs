@@ -0,0 +1,76 @@
+//confirm.rs
+use ratatui::layout::{Constraint, Layout, Rect};
+
+use crate::components::enums::ConfirmAction;
+
+/// The yes/no dialog that is open, and which button is selected.
+#[derive(Debug, Clone, Copy)]
+pub struct Confirm {
+    pub action: ConfirmAction,
+    //starts on "no" so a stray Enter doesn't do anything drastic
+    pub yes_selected: bool,
+    /// whose turn it was when the question was asked
+    pub turn: Option<usize>,
+}
+
+impl Confirm {
+    pub fn new(action: ConfirmAction, turn: Option<usize>) -> Confirm {
+        Confirm { action, yes_selected: false, turn }
+    }
+
+    pub fn toggle(&mut self) {
+        self.yes_selected = !self.yes_selected;
+    }
+
+    /// Splits the dialog into the message on top and the yes and no buttons below it.
+    pub fn layout(area: Rect) -> (Rect, Rect, Rect) {
+        let [message, buttons] = Layout::vertical([Constraint::Fill(1), Constraint::Length(3)])
+            .margin(1)
+            .areas(area);
+        let [yes, no] = Layout::horizontal([Constraint::Fill(1), Constraint::Fill(1)])
+            .spacing(2)
+            .areas(buttons);
+        (message, yes, no)
+    }
+}
+
+impl ConfirmAction {
+    pub fn title(&self) -> &'static str {
+        match self {
+            ConfirmAction::QuitToTitle => "Quit to title",
+            ConfirmAction::ShootSelf => "Shoot yourself",
+            ConfirmAction::Forfeit => "Forfeit",
+            ConfirmAction::OverwriteSave => "Save",
+            ConfirmAction::DoubleOrNothing => "Double or nothing",
+        }
+    }
+
+    pub fn message(&self) -> &'static str {
+        match self {
+            ConfirmAction::QuitToTitle => "A match is still going. Quit anyway? Anything not saved is lost.",
+            ConfirmAction::ShootSelf => "Point the shotgun at yourself?",
+            ConfirmAction::Forfeit => "Give up the match?",
+            ConfirmAction::OverwriteSave => "There is already a saved game. Save over it?",
+            ConfirmAction::DoubleOrNothing => "Play the next stage for double? Losing it loses the whole pot.",
+        }
+    }
+
+    /// Label of the button that goes ahead with the action.
+    pub fn yes_label(&self) -> &'static str {
+        match self {
+            ConfirmAction::QuitToTitle => "Quit",
+            ConfirmAction::ShootSelf => "Pull the trigger",
+            ConfirmAction::Forfeit => "Forfeit",
+            ConfirmAction::OverwriteSave => "Overwrite",
+            ConfirmAction::DoubleOrNothing => "Double",
+        }
+    }
+
+    /// Label of the button that backs out.
+    pub fn no_label(&self) -> &'static str {
+        match self {
+            ConfirmAction::DoubleOrNothing => "Cash out",
+            _ => "Cancel",
+        }
+    }
+}
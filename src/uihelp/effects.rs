@@ -0,0 +1,117 @@
+//effects.rs
+use std::time::Duration;
+
+use ratatui::style::Color;
+
+use crate::assets::AssetPack;
+use crate::components::shotgun::Shell;
+use crate::ui;
+use crate::uihelp::animation::{AnimFrame, Animation, Easing};
+use crate::uihelp::widget_data::WidgetKind;
+
+const FRAME: Duration = Duration::from_millis(66);
+
+/// The animation the shotgun plays after firing `shell`.
+///
+/// With `reduce_motion` every effect is a single still frame without shake or flicker.
+pub fn shot_animation(shell: &Shell, pack: &AssetPack, reduce_motion: bool) -> Animation {
+    let (bang, click) = (pack.bang.as_str(), pack.click.as_str());
+    let banner = |text: &str| with_banner(text, &pack.shotgun);
+    let frames = if reduce_motion {
+        vec![still_frame(shell, pack)]
+    } else {
+        match shell {
+            Shell::Live => vec![
+                AnimFrame::new(bang, FRAME).color(Color::Yellow).offset(2, 0),
+                AnimFrame::new(bang, FRAME).color(Color::LightRed).offset(-2, 1),
+                AnimFrame::new(bang, FRAME).color(Color::Red).offset(1, -1),
+                AnimFrame::new(bang, FRAME).color(Color::Red).offset(-1, 0),
+                AnimFrame::new(bang, FRAME * 3).color(Color::Red),
+            ],
+            Shell::Blank => vec![
+                AnimFrame::new(click, FRAME * 6).color(Color::DarkGray),
+            ],
+            Shell::Taser => (0..6)
+                .map(|i| {
+                    let color = if i % 2 == 0 { Color::LightCyan } else { Color::Yellow };
+                    AnimFrame::new(banner("⚡ Z Z Z T ⚡"), FRAME).color(color)
+                })
+                .collect(),
+            Shell::Poison => vec![
+                AnimFrame::new(banner("~ h i s s ~"), FRAME * 2).color(Color::LightGreen),
+                AnimFrame::new(banner("~ ~ h i s s ~ ~"), FRAME * 2).color(Color::Green),
+                AnimFrame::new(banner("☠ ~ ~ h i s s ~ ~ ☠"), FRAME * 3).color(Color::Green),
+            ],
+            Shell::BeanBag => vec![
+                AnimFrame::new(banner("T H U D"), FRAME).color(Color::Magenta).offset(-1, 0),
+                AnimFrame::new(banner("T H U D"), FRAME * 4).color(Color::Magenta),
+            ],
+            //looks like a blank until it goes off
+            Shell::Imposter => vec![
+                AnimFrame::new(click, FRAME * 3).color(Color::DarkGray),
+                AnimFrame::new(bang, FRAME).color(Color::Yellow).offset(2, 0),
+                AnimFrame::new(bang, FRAME).color(Color::Red).offset(-2, 0),
+                AnimFrame::new(bang, FRAME * 2).color(Color::Red),
+            ],
+            Shell::SelfDestruct => vec![
+                AnimFrame::new(bang, FRAME).color(Color::White).offset(3, 1),
+                AnimFrame::new(bang, FRAME).color(Color::Yellow).offset(-3, -1),
+                AnimFrame::new(bang, FRAME).color(Color::LightRed).offset(2, 1),
+                AnimFrame::new(bang, FRAME).color(Color::Red).offset(-2, 0),
+                AnimFrame::new(bang, FRAME * 4).color(Color::Red),
+            ],
+        }
+    };
+
+    Animation::new(WidgetKind::Shotgun, frames).easing(Easing::EaseOut)
+}
+
+/// The player board flashing red after someone was hurt.
+pub fn damage_flash(reduce_motion: bool) -> Animation {
+    let frames = if reduce_motion {
+        vec![AnimFrame::flash(Color::Red, FRAME * 4)]
+    } else {
+        vec![
+            AnimFrame::flash(Color::Red, FRAME * 2),
+            AnimFrame::flash(Color::LightRed, FRAME),
+            AnimFrame::flash(Color::Red, FRAME * 2),
+        ]
+    };
+    Animation::new(WidgetKind::Player, frames)
+}
+
+/// The shell in the chamber coming into view under the magnifying glass, `shell` is what the
+/// player sees.
+pub fn reveal_animation(shell: &Shell, pack: &AssetPack, reduce_motion: bool) -> Animation {
+    let banner = |text: &str| with_banner(text, &pack.shotgun);
+    let seen = banner(&format!("🔍 {} {:?}", pack.shell_icon(shell), shell));
+    let frames = if reduce_motion {
+        vec![AnimFrame::new(seen, FRAME * 12).color(ui::shell_color(shell))]
+    } else {
+        vec![
+            AnimFrame::new(banner("🔍 ."), FRAME * 2),
+            AnimFrame::new(banner("🔍 . ."), FRAME * 2),
+            AnimFrame::new(banner("🔍 . . ."), FRAME * 2),
+            AnimFrame::new(seen, FRAME * 10).color(ui::shell_color(shell)),
+        ]
+    };
+    //the dots go by quickly and the shell stays up
+    Animation::new(WidgetKind::Shotgun, frames).easing(Easing::EaseOut)
+}
+
+fn still_frame(shell: &Shell, pack: &AssetPack) -> AnimFrame {
+    let duration = FRAME * 6;
+    let banner = |text: &str| with_banner(text, &pack.shotgun);
+    match shell {
+        Shell::Live | Shell::Imposter | Shell::SelfDestruct => AnimFrame::new(pack.bang.as_str(), duration).color(Color::Red),
+        Shell::Blank => AnimFrame::new(pack.click.as_str(), duration).color(Color::DarkGray),
+        Shell::Taser => AnimFrame::new(banner("⚡ Z Z Z T ⚡"), duration).color(Color::LightCyan),
+        Shell::Poison => AnimFrame::new(banner("☠ h i s s ☠"), duration).color(Color::Green),
+        Shell::BeanBag => AnimFrame::new(banner("T H U D"), duration).color(Color::Magenta),
+    }
+}
+
+/// Puts a line of text above the shotgun art.
+fn with_banner(text: &str, shotgun: &str) -> String {
+    format!("\n   {text}{shotgun}")
+}
@@ -0,0 +1,262 @@
+//focus.rs
+use crate::uihelp::registry;
+use crate::uihelp::widget_data::WidgetKind;
+
+/// How many panels focus can go back through when panels are closed.
+const HISTORY_LEN: usize = 16;
+
+/// Which panels are shown in what order, and which one has focus.
+///
+/// The stack is drawn bottom first, so raising a panel puts it in front of the others. While a
+/// modal panel is open nothing below it can be focused or raised until it is closed.
+#[derive(Debug, Clone, Default)]
+pub struct FocusManager {
+    //bottom first
+    stack: Vec<WidgetKind>,
+    //panels that had focus before the current one, most recent last
+    history: Vec<WidgetKind>,
+    current: Option<WidgetKind>,
+}
+
+impl FocusManager {
+    /// Starts with the panels that are shown from the start, lowest z first.
+    pub fn new() -> FocusManager {
+        let mut specs: Vec<_> = registry::REGISTRY.iter().filter(|spec| spec.displayed).collect();
+        specs.sort_by_key(|spec| spec.placement.z);
+        FocusManager {
+            stack: specs.iter().map(|spec| spec.kind).collect(),
+            history: Vec::new(),
+            current: None,
+        }
+    }
+
+    pub fn stack(&self) -> &[WidgetKind] {
+        &self.stack
+    }
+
+    pub fn current(&self) -> Option<WidgetKind> {
+        self.current
+    }
+
+    pub fn is_open(&self, kind: WidgetKind) -> bool {
+        self.stack.contains(&kind)
+    }
+
+    /// The topmost open modal panel, it keeps focus until it is closed.
+    pub fn modal(&self) -> Option<WidgetKind> {
+        self.stack.iter().rev().copied().find(|kind| registry::spec(*kind).modal)
+    }
+
+    /// Puts a panel on top and focuses it. Only a modal can open over another modal.
+    pub fn open(&mut self, kind: WidgetKind) -> bool {
+        if self.modal().is_some() && !registry::spec(kind).modal {
+            return false;
+        }
+        self.stack.retain(|k| *k != kind);
+        self.stack.push(kind);
+        self.focus(kind)
+    }
+
+    /// Removes a panel, focus goes back to the last panel that had it and is still open.
+    pub fn close(&mut self, kind: WidgetKind) {
+        self.stack.retain(|k| *k != kind);
+        self.history.retain(|k| *k != kind);
+        if self.current == Some(kind) {
+            self.current = self.history.pop();
+        }
+    }
+
+    /// Focuses a panel, movable ones are also raised.
+    pub fn focus(&mut self, kind: WidgetKind) -> bool {
+        if !self.can_focus(kind) {
+            return false;
+        }
+        if let Some(previous) = self.current
+            && previous != kind
+        {
+            self.history.retain(|k| *k != previous && *k != kind);
+            self.history.push(previous);
+            if self.history.len() > HISTORY_LEN {
+                self.history.remove(0);
+            }
+        }
+        self.current = Some(kind);
+        if registry::spec(kind).movable {
+            self.raise(kind);
+        }
+        true
+    }
+
+    /// Focuses a panel, or gives focus back to the previous one if it already has it.
+    pub fn toggle(&mut self, kind: WidgetKind) {
+        if self.current == Some(kind) {
+            if self.modal().is_none() {
+                self.current = self.history.pop();
+            }
+        } else {
+            self.focus(kind);
+        }
+    }
+
+    /// Moves focus to the panel after (or before) the focused one in `order`, wrapping around.
+    pub fn cycle(&mut self, order: &[WidgetKind], forward: bool) {
+        if self.modal().is_some() || order.is_empty() {
+            return;
+        }
+        let len = order.len();
+        let start = match self.current.and_then(|kind| order.iter().position(|k| *k == kind)) {
+            Some(idx) if forward => idx + 1,
+            Some(idx) => idx + len - 1,
+            None if forward => 0,
+            None => len - 1,
+        };
+        self.focus(order[start % len]);
+    }
+
+    pub fn raise(&mut self, kind: WidgetKind) {
+        if self.modal().is_some_and(|modal| modal != kind) {
+            return;
+        }
+        if let Some(idx) = self.stack.iter().position(|k| *k == kind) {
+            let kind = self.stack.remove(idx);
+            self.stack.push(kind);
+        }
+    }
+
+    /// Sends a panel to the bottom of the stack, it keeps focus.
+    pub fn lower(&mut self, kind: WidgetKind) {
+        if self.modal() == Some(kind) {
+            return;
+        }
+        if let Some(idx) = self.stack.iter().position(|k| *k == kind) {
+            let kind = self.stack.remove(idx);
+            self.stack.insert(0, kind);
+        }
+    }
+
+    fn can_focus(&self, kind: WidgetKind) -> bool {
+        self.is_open(kind)
+            && registry::spec(kind).focusable
+            && self.modal().is_none_or(|modal| modal == kind)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn starts_with_the_panels_shown_from_the_start() {
+        let focus = FocusManager::new();
+        assert_eq!(focus.stack(), [WidgetKind::Shotgun]);
+        assert_eq!(focus.current(), None);
+    }
+
+    #[test]
+    fn focusing_a_movable_panel_raises_it() {
+        let mut focus = FocusManager::new();
+        focus.open(WidgetKind::Log);
+        focus.open(WidgetKind::Data);
+        assert_eq!(focus.stack(), [WidgetKind::Shotgun, WidgetKind::Log, WidgetKind::Data]);
+
+        assert!(focus.focus(WidgetKind::Log));
+        assert_eq!(focus.current(), Some(WidgetKind::Log));
+        assert_eq!(focus.stack().last(), Some(&WidgetKind::Log));
+
+        //docked panels take focus but stay where they are
+        assert!(focus.focus(WidgetKind::Shotgun));
+        assert_eq!(focus.stack().first(), Some(&WidgetKind::Shotgun));
+    }
+
+    #[test]
+    fn closing_gives_focus_back_to_the_last_open_panel() {
+        let mut focus = FocusManager::new();
+        focus.open(WidgetKind::Log);
+        focus.open(WidgetKind::Data);
+        focus.focus(WidgetKind::Shotgun);
+
+        //a closed panel is dropped from the history too
+        focus.close(WidgetKind::Data);
+        focus.close(WidgetKind::Shotgun);
+        assert_eq!(focus.current(), Some(WidgetKind::Log));
+        focus.close(WidgetKind::Log);
+        assert_eq!(focus.current(), None);
+    }
+
+    #[test]
+    fn a_modal_keeps_everything_below_it_out() {
+        let mut focus = FocusManager::new();
+        focus.open(WidgetKind::Log);
+        assert!(focus.open(WidgetKind::Pause));
+        assert_eq!(focus.modal(), Some(WidgetKind::Pause));
+
+        assert!(!focus.open(WidgetKind::Data));
+        assert!(!focus.focus(WidgetKind::Log));
+        focus.raise(WidgetKind::Log);
+        focus.cycle(&[WidgetKind::Log, WidgetKind::Shotgun], true);
+        focus.toggle(WidgetKind::Pause);
+        assert_eq!(focus.current(), Some(WidgetKind::Pause));
+        assert_eq!(focus.stack().last(), Some(&WidgetKind::Pause));
+
+        //a modal can still open over a modal
+        assert!(focus.open(WidgetKind::Confirm));
+        focus.close(WidgetKind::Confirm);
+        assert_eq!(focus.current(), Some(WidgetKind::Pause));
+        focus.close(WidgetKind::Pause);
+        assert_eq!(focus.current(), Some(WidgetKind::Log));
+    }
+
+    #[test]
+    fn unfocusable_panels_open_without_focus() {
+        let mut focus = FocusManager::new();
+        assert!(!focus.open(WidgetKind::Clock));
+        assert!(focus.is_open(WidgetKind::Clock));
+        assert_eq!(focus.current(), None);
+    }
+
+    #[test]
+    fn cycling_wraps_around_both_ways() {
+        let mut focus = FocusManager::new();
+        focus.open(WidgetKind::Log);
+        focus.open(WidgetKind::Data);
+        let order = [WidgetKind::Log, WidgetKind::Data, WidgetKind::Shotgun];
+
+        focus.cycle(&order, true);
+        assert_eq!(focus.current(), Some(WidgetKind::Shotgun));
+        focus.cycle(&order, true);
+        assert_eq!(focus.current(), Some(WidgetKind::Log));
+        focus.cycle(&order, false);
+        assert_eq!(focus.current(), Some(WidgetKind::Shotgun));
+    }
+
+    #[test]
+    fn toggling_the_focused_panel_goes_back_to_the_previous_one() {
+        let mut focus = FocusManager::new();
+        focus.open(WidgetKind::Log);
+        focus.toggle(WidgetKind::Shotgun);
+        assert_eq!(focus.current(), Some(WidgetKind::Shotgun));
+        focus.toggle(WidgetKind::Shotgun);
+        assert_eq!(focus.current(), Some(WidgetKind::Log));
+    }
+
+    #[test]
+    fn lowering_keeps_focus() {
+        let mut focus = FocusManager::new();
+        focus.open(WidgetKind::Log);
+        focus.lower(WidgetKind::Log);
+        assert_eq!(focus.stack(), [WidgetKind::Log, WidgetKind::Shotgun]);
+        assert_eq!(focus.current(), Some(WidgetKind::Log));
+    }
+
+    #[test]
+    fn switching_back_and_forth_keeps_the_history_short() {
+        let mut focus = FocusManager::new();
+        focus.open(WidgetKind::Log);
+        focus.open(WidgetKind::Data);
+        for _ in 0..HISTORY_LEN * 2 {
+            focus.focus(WidgetKind::Log);
+            focus.focus(WidgetKind::Data);
+        }
+        assert!(focus.history.len() <= 2);
+    }
+}
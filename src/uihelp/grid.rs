@@ -1,15 +1,142 @@
 use ratatui::{
     prelude::*,
-    widgets::{Block, Borders, Paragraph},
+    widgets::{Block, Borders, Paragraph, Wrap},
 };
 
-pub struct Grid {
+/// Splits its area into `cols` x `rows` equally sized cells.
+///
+/// Cells are filled left to right, top to bottom. Text cells are drawn by the widget itself,
+/// anything fancier can be drawn into the rects from [`Grid::cell_areas`].
+#[derive(Debug, Clone, Default)]
+pub struct Grid<'a> {
     pub cols: usize,
     pub rows: usize,
+    cells: Vec<Text<'a>>,
+    titles: Vec<Line<'a>>,
+    spacing: u16,
+    borders: bool,
+    selected: Option<usize>,
+    highlight_style: Style,
+    wrap: bool,
+    block: Option<Block<'a>>,
 }
 
-impl Widget for Grid {
+impl<'a> Grid<'a> {
+    pub fn new(cols: usize, rows: usize) -> Grid<'a> {
+        Grid {
+            cols,
+            rows,
+            highlight_style: Style::default().fg(Color::LightRed),
+            ..Default::default()
+        }
+    }
+
+    /// Content of every cell, extra cells past `cols * rows` are not drawn.
+    pub fn cells<T: Into<Text<'a>>>(mut self, cells: impl IntoIterator<Item = T>) -> Self {
+        self.cells = cells.into_iter().map(Into::into).collect();
+        self
+    }
+
+    /// Border titles of every cell, only shown when borders are on.
+    pub fn titles<T: Into<Line<'a>>>(mut self, titles: impl IntoIterator<Item = T>) -> Self {
+        self.titles = titles.into_iter().map(Into::into).collect();
+        self
+    }
+
+    /// Gap in cells between columns and rows.
+    pub fn spacing(mut self, spacing: u16) -> Self {
+        self.spacing = spacing;
+        self
+    }
+
+    /// Draws a border around every cell.
+    pub fn borders(mut self, borders: bool) -> Self {
+        self.borders = borders;
+        self
+    }
+
+    pub fn selected(mut self, selected: Option<usize>) -> Self {
+        self.selected = selected;
+        self
+    }
+
+    pub fn highlight_style<S: Into<Style>>(mut self, style: S) -> Self {
+        self.highlight_style = style.into();
+        self
+    }
+
+    /// Wraps cell text that is wider than its cell instead of cutting it off.
+    pub fn wrap(mut self, wrap: bool) -> Self {
+        self.wrap = wrap;
+        self
+    }
+
+    /// Block around the whole grid.
+    pub fn block(mut self, block: Block<'a>) -> Self {
+        self.block = Some(block);
+        self
+    }
+
+    /// Rects of every cell inside `area`, in the same order as [`Grid::cells`].
+    pub fn cell_areas(&self, area: Rect) -> Vec<Rect> {
+        if self.cols == 0 || self.rows == 0 {
+            return Vec::new();
+        }
+        let area = match &self.block {
+            Some(block) => block.inner(area),
+            None => area,
+        };
+
+        let rows = Layout::vertical(vec![Constraint::Fill(1); self.rows])
+            .spacing(self.spacing)
+            .split(area);
+
+        rows.iter()
+            .flat_map(|row| {
+                Layout::horizontal(vec![Constraint::Fill(1); self.cols])
+                    .spacing(self.spacing)
+                    .split(*row)
+                    .to_vec()
+            })
+            .collect()
+    }
+
+    /// Index of the cell that contains the given position, if any.
+    pub fn cell_at(&self, area: Rect, position: Position) -> Option<usize> {
+        self.cell_areas(area)
+            .iter()
+            .position(|cell| cell.contains(position))
+    }
+}
+
+impl Widget for Grid<'_> {
 
     fn render(self, area: Rect, buf: &mut Buffer) {
+        let cells = self.cell_areas(area);
+        if let Some(block) = &self.block {
+            block.clone().render(area, buf);
+        }
+
+        for (idx, cell) in cells.into_iter().enumerate() {
+            let style = if self.selected == Some(idx) {
+                self.highlight_style
+            } else {
+                Style::default()
+            };
+
+            let mut paragraph = Paragraph::new(self.cells.get(idx).cloned().unwrap_or_default())
+                .style(style);
+            if self.wrap {
+                paragraph = paragraph.wrap(Wrap { trim: true });
+            }
+            if self.borders {
+                let mut block = Block::default().borders(Borders::ALL).border_style(style);
+                if let Some(title) = self.titles.get(idx) {
+                    block = block.title(title.clone());
+                }
+                paragraph = paragraph.block(block);
+            }
+            paragraph.render(cell, buf);
+        }
     }
 }
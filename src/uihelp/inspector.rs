@@ -0,0 +1,271 @@
+//inspector.rs
+use std::collections::HashSet;
+
+use crate::app::App;
+use crate::components::shotgun::Shell;
+use crate::uihelp::registry::REGISTRY;
+use crate::uihelp::text;
+
+/// Something the inspector can change when cheats are on.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Editable {
+    /// a shell still in the chamber, by position in the round
+    Shell(usize),
+    /// a player's health, by index
+    Health(usize),
+}
+
+/// Moving around the inspector tree.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum InspectorAction {
+    Up,
+    Down,
+    PageUp,
+    PageDown,
+    /// open or close the selected node
+    Toggle,
+    Collapse,
+    /// select a visible row, opening it if it was already selected
+    Click(usize),
+}
+
+/// Changes made through the inspector in cheat mode.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Cheat {
+    /// cycle the shell at a position to the next kind
+    CycleShell(usize, bool),
+    /// swap the shell at a position with the one before or after it
+    MoveShell(usize, bool),
+    /// add to or take from a player's health
+    Health(usize, bool),
+}
+
+/// One node of the tree, built fresh from the app on every frame so it is always live.
+#[derive(Debug, Clone)]
+pub struct Node {
+    pub label: String,
+    pub children: Vec<Node>,
+    pub edit: Option<Editable>,
+}
+
+impl Node {
+    fn leaf(label: impl Into<String>) -> Node {
+        Node { label: label.into(), children: Vec::new(), edit: None }
+    }
+
+    fn branch(label: impl Into<String>, children: Vec<Node>) -> Node {
+        Node { label: label.into(), children, edit: None }
+    }
+
+    fn edit(mut self, edit: Editable) -> Node {
+        self.edit = Some(edit);
+        self
+    }
+
+    //values change every frame, so only the part before ':' identifies a node
+    fn label_key(&self) -> String {
+        self.label.split(':').next().unwrap_or_default().to_string()
+    }
+}
+
+/// A node as it is shown, after collapsed branches are left out.
+#[derive(Debug, Clone)]
+pub struct Row {
+    pub depth: usize,
+    //labels of the node and its parents, used to remember what is open
+    pub path: String,
+    pub label: String,
+    pub branch: bool,
+    pub open: bool,
+    pub edit: Option<Editable>,
+}
+
+/// What is open and selected in the inspector.
+#[derive(Debug, Clone)]
+pub struct Inspector {
+    open: HashSet<String>,
+    pub selected: usize,
+    pub scroll: usize,
+}
+
+impl Default for Inspector {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Inspector {
+    /// Starts with the top level open.
+    pub fn new() -> Inspector {
+        let open = ["Data", "MatchData", "WidgetData", "App"].map(String::from).into();
+        Inspector { open, selected: 0, scroll: 0 }
+    }
+
+    /// The visible rows of the tree.
+    pub fn rows(&self, tree: &[Node]) -> Vec<Row> {
+        let mut rows = Vec::new();
+        for node in tree {
+            self.flatten(node, 0, "", &mut rows);
+        }
+        rows
+    }
+
+    fn flatten(&self, node: &Node, depth: usize, parent: &str, rows: &mut Vec<Row>) {
+        let path = if parent.is_empty() { node.label_key() } else { format!("{parent}/{}", node.label_key()) };
+        let open = self.open.contains(&path);
+        rows.push(Row {
+            depth,
+            path: path.clone(),
+            label: node.label.clone(),
+            branch: !node.children.is_empty(),
+            open,
+            edit: node.edit,
+        });
+        if open {
+            for child in &node.children {
+                self.flatten(child, depth + 1, &path, rows);
+            }
+        }
+    }
+
+    /// The first row shown out of `len` in `height` lines, the tree can shrink under the view
+    /// while it updates.
+    pub fn top(&self, len: usize, height: u16) -> usize {
+        self.scroll.min(text::max_scroll(len, height))
+    }
+
+    /// Applies a navigation action, `height` is how many rows fit in the panel.
+    pub fn apply(&mut self, action: InspectorAction, rows: &[Row], height: usize) {
+        let last = rows.len().saturating_sub(1);
+        let page = height.max(1);
+        match action {
+            InspectorAction::Up => self.selected = self.selected.saturating_sub(1),
+            InspectorAction::Down => self.selected = (self.selected + 1).min(last),
+            InspectorAction::PageUp => self.selected = self.selected.saturating_sub(page),
+            InspectorAction::PageDown => self.selected = (self.selected + page).min(last),
+            InspectorAction::Toggle => self.toggle(rows),
+            InspectorAction::Collapse => {
+                if let Some(row) = rows.get(self.selected) {
+                    if row.open {
+                        self.open.remove(&row.path);
+                    } else if let Some(parent) = rows[..self.selected].iter().rposition(|r| r.depth < row.depth) {
+                        //closing a leaf jumps to its parent
+                        self.selected = parent;
+                    }
+                }
+            },
+            InspectorAction::Click(row) => {
+                if row == self.selected {
+                    self.toggle(rows);
+                }
+                self.selected = row.min(last);
+            },
+        }
+        self.selected = self.selected.min(last);
+
+        //keep the selection on screen
+        if self.selected < self.scroll {
+            self.scroll = self.selected;
+        } else if self.selected >= self.scroll + page {
+            self.scroll = self.selected + 1 - page;
+        }
+    }
+
+    fn toggle(&mut self, rows: &[Row]) {
+        if let Some(row) = rows.get(self.selected)
+            && row.branch
+            && !self.open.remove(&row.path)
+        {
+            self.open.insert(row.path.clone());
+        }
+    }
+}
+
+/// Builds the tree of everything worth looking at.
+pub fn tree(app: &App) -> Vec<Node> {
+    let shotgun = &app.data.shotgun;
+    let position = shotgun.position();
+    let chamber: Vec<Node> = (position..position + shotgun.shell_count())
+        .filter_map(|pos| shotgun.peek(pos).map(|shell| (pos, shell)))
+        .map(|(pos, shell)| Node::leaf(format!("#{pos}: {shell:?}")).edit(Editable::Shell(pos)))
+        .collect();
+    let fired: Vec<Node> = shotgun
+        .fired
+        .borrow()
+        .iter()
+        .enumerate()
+        .map(|(pos, shell)| Node::leaf(format!("#{pos}: {shell:?}")))
+        .collect();
+
+    let players: Vec<Node> = app
+        .data
+        .players
+        .iter()
+        .enumerate()
+        .map(|(idx, player)| {
+            Node::branch(
+                format!("{}: id {}", player.name, player.id),
+                vec![
+                    Node::leaf(format!("health: {}", player.health)).edit(Editable::Health(idx)),
+                    Node::leaf(format!("items: {:?}", player.items)),
+                    Node::leaf(format!("statuses: {:?}", player.statuses)),
+                    Node::leaf(format!("known: {:?}", player.known)),
+                ],
+            )
+        })
+        .collect();
+
+    let widget_data = &app.widget_data;
+    let widgets: Vec<Node> = REGISTRY
+        .iter()
+        .map(|spec| {
+            let kind = spec.kind;
+            Node::branch(
+                format!("{kind:?}: {}", if widget_data.is_displayed(kind) { "shown" } else { "hidden" }),
+                vec![
+                    Node::leaf(format!("area: {:?}", widget_data.area(kind))),
+                    Node::leaf(format!("floating: {:?}", widget_data.floating(kind))),
+                    Node::leaf(format!("color: {:?}", widget_data.get_color(&kind))),
+                ],
+            )
+        })
+        .collect();
+
+    vec![
+        Node::branch("Data", vec![
+            Node::branch("shotgun", vec![
+                Node::leaf(format!("model: {:?}", shotgun.model)),
+                Node::leaf(format!("state: {:?}", shotgun.state)),
+                Node::branch(format!("chamber: {} shells, next first", shotgun.shell_count()), chamber),
+                Node::branch(format!("fired: {}", fired.len()), fired),
+            ]),
+            Node::branch(format!("players: {}", players.len()), players),
+        ]),
+        Node::branch("MatchData", vec![
+            Node::leaf(format!("round: {}", app.match_data.count())),
+            Node::leaf(format!("turn: {:?}", app.match_data.turn())),
+            Node::leaf(format!("phase: {:?}", app.match_data.phase())),
+            Node::leaf(format!("pot: {} x{}", app.match_data.pot(), app.match_data.multiplier())),
+        ]),
+        Node::branch("WidgetData", vec![
+            Node::leaf(format!("focused: {:?}", widget_data.focused())),
+            Node::leaf(format!("modal: {:?}", widget_data.modal())),
+            Node::leaf(format!("stack: {:?}", widget_data.stack())),
+            Node::branch("widgets", widgets),
+        ]),
+        Node::branch("App", vec![
+            Node::leaf(format!("counter: {}", app.counter)),
+            Node::leaf(format!("log: {} lines", app.log.len())),
+            Node::leaf(format!("animating: {}", !app.animator.is_idle())),
+            Node::leaf(format!("inventory_selected: {}", app.inventory_selected)),
+            Node::leaf(format!("turn_timer: {:?}", app.turn_timer)),
+        ]),
+    ]
+}
+
+/// The next kind of shell when cycling through them in cheat mode.
+pub fn next_shell(shell: &Shell, forward: bool) -> Shell {
+    let idx = Shell::ALL.iter().position(|s| s == shell).unwrap_or(0);
+    let next = if forward { idx + 1 } else { idx + Shell::ALL.len() - 1 };
+    Shell::ALL[next % Shell::ALL.len()].clone()
+}
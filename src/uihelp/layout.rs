@@ -0,0 +1,290 @@
+//layout.rs
+use ratatui::layout::Rect;
+
+use crate::uihelp::widget_data::WidgetKind;
+
+/// Where a widget wants to sit inside the area it is given.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Anchor {
+    Center,
+    Top,
+    Bottom,
+    Left,
+    Right,
+    TopLeft,
+    TopRight,
+}
+
+/// How one axis of a widget is sized.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Size {
+    /// exact amount of cells
+    Fixed(u16),
+    /// percentage of the available area
+    Percent(u16),
+    /// whatever is left over
+    Fill,
+}
+
+/// A [`Size`] clamped between a minimum and a maximum.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct SizeRule {
+    pub size: Size,
+    pub min: u16,
+    pub max: u16,
+}
+
+impl SizeRule {
+    pub const fn fixed(cells: u16) -> SizeRule {
+        SizeRule { size: Size::Fixed(cells), min: 0, max: u16::MAX }
+    }
+
+    pub const fn percent(percent: u16) -> SizeRule {
+        SizeRule { size: Size::Percent(percent), min: 0, max: u16::MAX }
+    }
+
+    pub const fn fill() -> SizeRule {
+        SizeRule { size: Size::Fill, min: 0, max: u16::MAX }
+    }
+
+    pub const fn min(mut self, min: u16) -> SizeRule {
+        self.min = min;
+        self
+    }
+
+    pub const fn max(mut self, max: u16) -> SizeRule {
+        self.max = max;
+        self
+    }
+
+    /// Resolves the rule against the length that is available on that axis.
+    /// Never returns more than `available`, even if `min` asks for it.
+    pub fn resolve(&self, available: u16) -> u16 {
+        let wanted = match self.size {
+            Size::Fixed(cells) => cells,
+            Size::Percent(percent) => (available as u32 * percent.min(100) as u32 / 100) as u16,
+            Size::Fill => available,
+        };
+        wanted.clamp(self.min, self.max.max(self.min)).min(available)
+    }
+}
+
+/// Declares how a widget is placed on screen.
+///
+/// Docked widgets carve their space off an edge of the screen, in the order they are resolved,
+/// and everything after them only gets what is left. Floating widgets are anchored inside the
+/// area left by the docks and get moved out of the way of other floating widgets when possible.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Placement {
+    pub anchor: Anchor,
+    pub width: SizeRule,
+    pub height: SizeRule,
+    //higher is drawn later, so on top
+    pub z: u8,
+    pub docked: bool,
+}
+
+impl Placement {
+    pub const fn floating(anchor: Anchor, width: SizeRule, height: SizeRule, z: u8) -> Placement {
+        Placement { anchor, width, height, z, docked: false }
+    }
+
+    pub const fn docked(anchor: Anchor, width: SizeRule, height: SizeRule, z: u8) -> Placement {
+        Placement { anchor, width, height, z, docked: true }
+    }
+}
+
+/// Resolves [`Placement`]s into concrete [`Rect`]s for the current terminal size.
+///
+/// Nothing is cached, so calling it every frame is what makes the layout follow terminal resizes.
+pub struct LayoutEngine;
+
+impl LayoutEngine {
+    /// Returns one rect per widget, sorted by z-order (lowest first) so they can be drawn in order.
+    /// Widgets with the same z keep the order they were passed in.
+    pub fn resolve(area: Rect, widgets: &[(WidgetKind, Placement)]) -> Vec<(WidgetKind, Rect)> {
+        let mut remaining = area;
+        let mut placed: Vec<(WidgetKind, Rect, u8)> = Vec::new();
+
+        //docks first, they decide how much room the floating widgets get
+        for (kind, placement) in widgets.iter().filter(|(_, p)| p.docked) {
+            let rect = Self::dock(&mut remaining, placement);
+            placed.push((*kind, rect, placement.z));
+        }
+
+        let mut floating: Vec<Rect> = Vec::new();
+        for (kind, placement) in widgets.iter().filter(|(_, p)| !p.docked) {
+            let rect = Self::anchor(remaining, placement);
+            let rect = Self::avoid_overlap(rect, remaining, placement.anchor, &floating);
+            floating.push(rect);
+            placed.push((*kind, rect, placement.z));
+        }
+
+        //stable sort keeps the caller's order for equal z
+        placed.sort_by_key(|(_, _, z)| *z);
+        placed.into_iter().map(|(kind, rect, _)| (kind, rect)).collect()
+    }
+
+    fn dock(remaining: &mut Rect, placement: &Placement) -> Rect {
+        let area = *remaining;
+        match placement.anchor {
+            Anchor::Top | Anchor::TopLeft | Anchor::TopRight => {
+                let height = placement.height.resolve(area.height);
+                remaining.y += height;
+                remaining.height -= height;
+                Rect { height, ..area }
+            },
+            Anchor::Bottom => {
+                let height = placement.height.resolve(area.height);
+                remaining.height -= height;
+                Rect { y: area.y + area.height - height, height, ..area }
+            },
+            Anchor::Left => {
+                let width = placement.width.resolve(area.width);
+                remaining.x += width;
+                remaining.width -= width;
+                Rect { width, ..area }
+            },
+            Anchor::Right => {
+                let width = placement.width.resolve(area.width);
+                remaining.width -= width;
+                Rect { x: area.x + area.width - width, width, ..area }
+            },
+            //docking to the center just takes everything that is left
+            Anchor::Center => {
+                *remaining = Rect { width: 0, height: 0, ..area };
+                area
+            },
+        }
+    }
+
+    fn anchor(area: Rect, placement: &Placement) -> Rect {
+        let width = placement.width.resolve(area.width);
+        let height = placement.height.resolve(area.height);
+
+        let left = area.x;
+        let center_x = area.x + (area.width - width) / 2;
+        let right = area.x + area.width - width;
+        let top = area.y;
+        let center_y = area.y + (area.height - height) / 2;
+        let bottom = area.y + area.height - height;
+
+        let (x, y) = match placement.anchor {
+            Anchor::Center => (center_x, center_y),
+            Anchor::Top => (center_x, top),
+            Anchor::Bottom => (center_x, bottom),
+            Anchor::Left => (left, center_y),
+            Anchor::Right => (right, center_y),
+            Anchor::TopLeft => (left, top),
+            Anchor::TopRight => (right, top),
+        };
+        Rect { x, y, width, height }
+    }
+
+    /// Slides `rect` away from its anchor until it no longer overlaps anything in `taken`.
+    /// If there is no free spot inside `bounds` it stays where it was and z-order decides.
+    fn avoid_overlap(rect: Rect, bounds: Rect, anchor: Anchor, taken: &[Rect]) -> Rect {
+        let overlaps = |r: &Rect| taken.iter().any(|t| t.intersects(*r));
+        if !overlaps(&rect) {
+            return rect;
+        }
+
+        //move along the axis that points away from the anchored edge
+        let (dx, dy): (i32, i32) = match anchor {
+            Anchor::Top | Anchor::Left | Anchor::Right | Anchor::TopLeft | Anchor::TopRight => (0, 1),
+            Anchor::Bottom => (0, -1),
+            Anchor::Center => (1, 0),
+        };
+
+        let mut candidate = rect;
+        loop {
+            let x = candidate.x as i32 + dx;
+            let y = candidate.y as i32 + dy;
+            if x < bounds.x as i32
+                || y < bounds.y as i32
+                || x + candidate.width as i32 > bounds.right() as i32
+                || y + candidate.height as i32 > bounds.bottom() as i32
+            {
+                return rect;
+            }
+            candidate.x = x as u16;
+            candidate.y = y as u16;
+            if !overlaps(&candidate) {
+                return candidate;
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const SCREEN: Rect = Rect { x: 0, y: 0, width: 100, height: 40 };
+
+    fn rect_of(placed: &[(WidgetKind, Rect)], kind: WidgetKind) -> Rect {
+        placed.iter().find(|(k, _)| *k == kind).map(|(_, rect)| *rect).unwrap()
+    }
+
+    #[test]
+    fn size_rules_are_clamped_and_never_exceed_the_space() {
+        assert_eq!(SizeRule::fixed(10).resolve(50), 10);
+        assert_eq!(SizeRule::percent(20).resolve(50), 10);
+        assert_eq!(SizeRule::percent(20).min(12).resolve(50), 12);
+        assert_eq!(SizeRule::fill().max(30).resolve(50), 30);
+        assert_eq!(SizeRule::fixed(80).resolve(50), 50);
+        assert_eq!(SizeRule::fixed(2).min(12).resolve(8), 8);
+    }
+
+    #[test]
+    fn docks_carve_their_edge_in_order() {
+        let placed = LayoutEngine::resolve(SCREEN, &[
+            (WidgetKind::Player, Placement::docked(Anchor::Left, SizeRule::fixed(20), SizeRule::fill(), 0)),
+            (WidgetKind::Inventory, Placement::docked(Anchor::Bottom, SizeRule::fill(), SizeRule::fixed(8), 0)),
+        ]);
+        assert_eq!(rect_of(&placed, WidgetKind::Player), Rect::new(0, 0, 20, 40));
+        //the second dock only gets what the first one left
+        assert_eq!(rect_of(&placed, WidgetKind::Inventory), Rect::new(20, 32, 80, 8));
+    }
+
+    #[test]
+    fn floating_widgets_anchor_inside_what_the_docks_left() {
+        let placed = LayoutEngine::resolve(SCREEN, &[
+            (WidgetKind::Player, Placement::docked(Anchor::Top, SizeRule::fill(), SizeRule::fixed(10), 0)),
+            (WidgetKind::Shotgun, Placement::floating(Anchor::Center, SizeRule::fixed(40), SizeRule::fixed(10), 0)),
+            (WidgetKind::Clock, Placement::floating(Anchor::TopRight, SizeRule::fixed(20), SizeRule::fixed(3), 0)),
+        ]);
+        assert_eq!(rect_of(&placed, WidgetKind::Shotgun), Rect::new(30, 20, 40, 10));
+        assert_eq!(rect_of(&placed, WidgetKind::Clock), Rect::new(80, 10, 20, 3));
+    }
+
+    #[test]
+    fn overlapping_floating_widgets_slide_away_from_their_anchor() {
+        let placed = LayoutEngine::resolve(SCREEN, &[
+            (WidgetKind::Log, Placement::floating(Anchor::TopLeft, SizeRule::fixed(30), SizeRule::fixed(10), 0)),
+            (WidgetKind::Data, Placement::floating(Anchor::TopLeft, SizeRule::fixed(30), SizeRule::fixed(10), 0)),
+        ]);
+        assert_eq!(rect_of(&placed, WidgetKind::Log), Rect::new(0, 0, 30, 10));
+        assert_eq!(rect_of(&placed, WidgetKind::Data), Rect::new(0, 10, 30, 10));
+    }
+
+    #[test]
+    fn a_widget_without_a_free_spot_stays_put() {
+        let placed = LayoutEngine::resolve(SCREEN, &[
+            (WidgetKind::Log, Placement::floating(Anchor::Top, SizeRule::fill(), SizeRule::fill(), 0)),
+            (WidgetKind::Data, Placement::floating(Anchor::Top, SizeRule::fixed(30), SizeRule::fixed(10), 0)),
+        ]);
+        assert_eq!(rect_of(&placed, WidgetKind::Data), Rect::new(35, 0, 30, 10));
+    }
+
+    #[test]
+    fn results_are_sorted_by_z_keeping_the_order_of_equal_ones() {
+        let placed = LayoutEngine::resolve(SCREEN, &[
+            (WidgetKind::Pause, Placement::floating(Anchor::Center, SizeRule::fixed(10), SizeRule::fixed(5), 2)),
+            (WidgetKind::Log, Placement::floating(Anchor::Left, SizeRule::fixed(10), SizeRule::fixed(5), 0)),
+            (WidgetKind::Data, Placement::floating(Anchor::Right, SizeRule::fixed(10), SizeRule::fixed(5), 0)),
+        ]);
+        let order: Vec<WidgetKind> = placed.iter().map(|(kind, _)| *kind).collect();
+        assert_eq!(order, [WidgetKind::Log, WidgetKind::Data, WidgetKind::Pause]);
+    }
+}
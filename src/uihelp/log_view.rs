@@ -0,0 +1,277 @@
+//log_view.rs
+use std::collections::VecDeque;
+
+use serde::{Deserialize, Serialize};
+
+use crate::uihelp::text;
+
+/// A message in the log and the round it was sent in.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct LogEntry {
+    pub round: u8,
+    pub text: String,
+}
+
+/// A line the log panel shows, messages plus a divider whenever the round changes.
+#[derive(Debug, Clone)]
+pub struct LogLine {
+    pub text: String,
+    pub divider: bool,
+}
+
+/// Moving around and searching the log.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum LogAction {
+    Up,
+    Down,
+    PageUp,
+    PageDown,
+    /// the oldest message
+    Home,
+    /// the newest message, turns follow back on
+    End,
+    /// start typing a search
+    Search,
+    SearchInput(char),
+    SearchBackspace,
+    /// stop typing, the matches stay highlighted
+    SearchDone,
+    /// stop searching and clear the highlights
+    SearchCancel,
+    NextMatch,
+    PrevMatch,
+}
+
+/// Scroll position and search of the log panel.
+///
+/// Scrolling is counted in wrapped rows. While following, the view sticks to the newest message.
+#[derive(Debug, Clone)]
+pub struct LogView {
+    scroll: usize,
+    pub follow: bool,
+    pub query: String,
+    pub searching: bool,
+}
+
+impl Default for LogView {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl LogView {
+    pub fn new() -> LogView {
+        LogView { scroll: 0, follow: true, query: String::new(), searching: false }
+    }
+
+    /// The lines to show, with a divider in front of every round.
+    pub fn lines(log: &VecDeque<LogEntry>) -> Vec<LogLine> {
+        let mut lines = Vec::new();
+        let mut round = None;
+        for entry in log {
+            if round != Some(entry.round) {
+                round = Some(entry.round);
+                lines.push(LogLine { text: format!("── Round {} ──", entry.round), divider: true });
+            }
+            lines.push(LogLine { text: entry.text.clone(), divider: false });
+        }
+        lines
+    }
+
+    /// How far down the view is, clamped to the content.
+    pub fn scroll(&self, lines: &[LogLine], width: u16, height: u16) -> usize {
+        let max = max_scroll(lines, width, height);
+        if self.follow { max } else { self.scroll.min(max) }
+    }
+
+    /// Whether a line contains the search, ignoring case.
+    pub fn matches(&self, text: &str) -> bool {
+        !self.query.is_empty() && text.to_lowercase().contains(&self.query.to_lowercase())
+    }
+
+    /// `width` and `height` are the inside of the panel, without the scrollbar.
+    pub fn apply(&mut self, action: LogAction, lines: &[LogLine], width: u16, height: u16) {
+        let max = max_scroll(lines, width, height);
+        let page = (height as usize).max(1);
+        let scroll = self.scroll(lines, width, height);
+        let target = match action {
+            LogAction::Up => Some(scroll.saturating_sub(1)),
+            LogAction::Down => Some(scroll + 1),
+            LogAction::PageUp => Some(scroll.saturating_sub(page)),
+            LogAction::PageDown => Some(scroll + page),
+            LogAction::Home => Some(0),
+            LogAction::End => Some(max),
+            LogAction::Search => {
+                self.searching = true;
+                self.query.clear();
+                None
+            },
+            LogAction::SearchInput(c) => {
+                self.query.push(c);
+                //jump to the newest match while typing
+                self.match_rows(lines, width).last().copied()
+            },
+            LogAction::SearchBackspace => {
+                self.query.pop();
+                self.match_rows(lines, width).last().copied()
+            },
+            LogAction::SearchDone => {
+                self.searching = false;
+                None
+            },
+            LogAction::SearchCancel => {
+                self.searching = false;
+                self.query.clear();
+                None
+            },
+            LogAction::NextMatch => self.match_rows(lines, width).into_iter().find(|row| *row > scroll),
+            LogAction::PrevMatch => self.match_rows(lines, width).into_iter().rev().find(|row| *row < scroll),
+        };
+
+        if let Some(target) = target {
+            self.scroll = target.min(max);
+            //scrolling to the bottom picks up new messages again
+            self.follow = self.scroll == max;
+        }
+    }
+
+    /// The wrapped row every matching line starts on.
+    fn match_rows(&self, lines: &[LogLine], width: u16) -> Vec<usize> {
+        let mut row = 0;
+        let mut rows = Vec::new();
+        for line in lines {
+            if !line.divider && self.matches(&line.text) {
+                rows.push(row);
+            }
+            row += text::wrapped_height(line.text.as_str(), width);
+        }
+        rows
+    }
+}
+
+/// How many rows the lines take up once wrapped to `width`.
+pub fn content_height(lines: &[LogLine], width: u16) -> usize {
+    lines.iter().map(|line| text::wrapped_height(line.text.as_str(), width)).sum()
+}
+
+fn max_scroll(lines: &[LogLine], width: u16, height: u16) -> usize {
+    text::max_scroll(content_height(lines, width), height)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const WIDTH: u16 = 40;
+    const HEIGHT: u16 = 4;
+
+    /// Ten messages over two rounds, that is twelve rows with the dividers.
+    fn log() -> VecDeque<LogEntry> {
+        (0..10)
+            .map(|idx| LogEntry { round: if idx < 5 { 1 } else { 2 }, text: format!("message {idx}") })
+            .collect()
+    }
+
+    #[test]
+    fn every_round_starts_with_a_divider() {
+        let lines = LogView::lines(&log());
+        assert_eq!(lines.len(), 12);
+        assert!(lines[0].divider && lines[0].text.contains("Round 1"));
+        assert!(lines[6].divider && lines[6].text.contains("Round 2"));
+        assert_eq!(lines[7].text, "message 5");
+    }
+
+    #[test]
+    fn follows_the_newest_message_until_scrolled_up() {
+        let lines = LogView::lines(&log());
+        let mut view = LogView::new();
+        assert_eq!(view.scroll(&lines, WIDTH, HEIGHT), 8);
+
+        view.apply(LogAction::Up, &lines, WIDTH, HEIGHT);
+        assert!(!view.follow);
+        assert_eq!(view.scroll(&lines, WIDTH, HEIGHT), 7);
+
+        //scrolling back down to the bottom follows again
+        view.apply(LogAction::Down, &lines, WIDTH, HEIGHT);
+        assert!(view.follow);
+    }
+
+    #[test]
+    fn paging_stays_inside_the_content() {
+        let lines = LogView::lines(&log());
+        let mut view = LogView::new();
+        view.apply(LogAction::Home, &lines, WIDTH, HEIGHT);
+        assert_eq!(view.scroll(&lines, WIDTH, HEIGHT), 0);
+        view.apply(LogAction::PageUp, &lines, WIDTH, HEIGHT);
+        assert_eq!(view.scroll(&lines, WIDTH, HEIGHT), 0);
+        view.apply(LogAction::PageDown, &lines, WIDTH, HEIGHT);
+        assert_eq!(view.scroll(&lines, WIDTH, HEIGHT), 4);
+        view.apply(LogAction::PageDown, &lines, WIDTH, HEIGHT);
+        view.apply(LogAction::PageDown, &lines, WIDTH, HEIGHT);
+        assert_eq!(view.scroll(&lines, WIDTH, HEIGHT), 8);
+        assert!(view.follow);
+    }
+
+    #[test]
+    fn wrapped_lines_count_every_row() {
+        let lines = vec![LogLine { text: "a".repeat(25), divider: false }; 2];
+        assert_eq!(content_height(&lines, 10), 6);
+        let mut view = LogView::new();
+        assert_eq!(view.scroll(&lines, 10, HEIGHT), 2);
+        view.apply(LogAction::Home, &lines, 10, HEIGHT);
+        view.apply(LogAction::End, &lines, 10, HEIGHT);
+        assert_eq!(view.scroll(&lines, 10, HEIGHT), 2);
+    }
+
+    #[test]
+    fn typing_a_search_jumps_to_the_newest_match() {
+        let lines = LogView::lines(&log());
+        let mut view = LogView::new();
+        view.apply(LogAction::Home, &lines, WIDTH, HEIGHT);
+        view.apply(LogAction::Search, &lines, WIDTH, HEIGHT);
+        assert!(view.searching);
+        for c in "MESSAGE 2".chars() {
+            view.apply(LogAction::SearchInput(c), &lines, WIDTH, HEIGHT);
+        }
+        assert_eq!(view.scroll(&lines, WIDTH, HEIGHT), 3);
+        assert!(view.matches("message 2"));
+        //dividers are never matches
+        assert!(!view.matches(&lines[0].text));
+    }
+
+    #[test]
+    fn next_and_previous_match_step_through_the_log() {
+        let lines = LogView::lines(&log());
+        let mut view = LogView::new();
+        view.query = String::from("message");
+        view.apply(LogAction::Home, &lines, WIDTH, HEIGHT);
+
+        view.apply(LogAction::NextMatch, &lines, WIDTH, HEIGHT);
+        assert_eq!(view.scroll(&lines, WIDTH, HEIGHT), 1);
+        view.apply(LogAction::NextMatch, &lines, WIDTH, HEIGHT);
+        assert_eq!(view.scroll(&lines, WIDTH, HEIGHT), 2);
+        view.apply(LogAction::PrevMatch, &lines, WIDTH, HEIGHT);
+        assert_eq!(view.scroll(&lines, WIDTH, HEIGHT), 1);
+        //no earlier match, the view stays
+        view.apply(LogAction::PrevMatch, &lines, WIDTH, HEIGHT);
+        assert_eq!(view.scroll(&lines, WIDTH, HEIGHT), 1);
+    }
+
+    #[test]
+    fn cancelling_clears_the_search_and_done_keeps_it() {
+        let lines = LogView::lines(&log());
+        let mut view = LogView::new();
+        view.apply(LogAction::Search, &lines, WIDTH, HEIGHT);
+        view.apply(LogAction::SearchInput('m'), &lines, WIDTH, HEIGHT);
+        view.apply(LogAction::SearchDone, &lines, WIDTH, HEIGHT);
+        assert!(!view.searching);
+        assert_eq!(view.query, "m");
+
+        view.apply(LogAction::SearchBackspace, &lines, WIDTH, HEIGHT);
+        assert!(view.query.is_empty());
+        view.apply(LogAction::SearchInput('m'), &lines, WIDTH, HEIGHT);
+        view.apply(LogAction::SearchCancel, &lines, WIDTH, HEIGHT);
+        assert!(view.query.is_empty());
+        assert!(!view.matches("message 1"));
+    }
+}
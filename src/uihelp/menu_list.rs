@@ -0,0 +1,72 @@
+//menu_list.rs
+use ratatui::{
+    layout::{Position, Rect},
+    style::Style,
+    text::Line,
+    widgets::Paragraph,
+    Frame,
+};
+
+/// A column of rows one line each, like the pages of the pause menu and the title screen.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct MenuList {
+    /// where the rows are drawn
+    pub area: Rect,
+    pub selected: usize,
+    /// how many rows there are
+    pub rows: usize,
+}
+
+impl MenuList {
+    pub fn new(area: Rect, selected: usize, rows: usize) -> MenuList {
+        MenuList { area, selected, rows }
+    }
+
+    /// The first row shown, so the selected one stays in view on short screens.
+    pub fn offset(&self) -> usize {
+        self.selected.saturating_sub((self.area.height as usize).saturating_sub(1))
+    }
+
+    /// The row at a position, if there is one.
+    pub fn row_at(&self, column: u16, row: u16) -> Option<usize> {
+        if !self.area.contains(Position::new(column, row)) {
+            return None;
+        }
+        let idx = self.offset() + (row - self.area.y) as usize;
+        (idx < self.rows).then_some(idx)
+    }
+
+    /// A label on the left with its value on the right edge.
+    pub fn labelled<'a>(&self, label: &str, value: &str, style: Style) -> Line<'a> {
+        let pad = (self.area.width as usize).saturating_sub(label.chars().count() + value.chars().count());
+        Line::styled(format!("{label}{}{value}", " ".repeat(pad)), style)
+    }
+
+    /// Draws `lines` scrolled to the selected row.
+    pub fn render(&self, frame: &mut Frame, lines: Vec<Line>) {
+        frame.render_widget(Paragraph::new(lines).scroll((self.offset() as u16, 0)), self.area);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn the_selected_row_stays_in_view() {
+        let mut list = MenuList::new(Rect::new(0, 10, 20, 3), 0, 8);
+        assert_eq!(list.offset(), 0);
+        assert_eq!(list.row_at(5, 12), Some(2));
+        list.selected = 6;
+        assert_eq!(list.offset(), 4);
+        assert_eq!(list.row_at(5, 10), Some(4));
+        assert_eq!(list.row_at(5, 13), None);
+    }
+
+    #[test]
+    fn rows_past_the_end_are_not_hit() {
+        let list = MenuList::new(Rect::new(0, 0, 20, 5), 0, 2);
+        assert_eq!(list.row_at(0, 1), Some(1));
+        assert_eq!(list.row_at(0, 2), None);
+    }
+}
@@ -1 +1,19 @@
 pub mod widget_data;
+pub mod layout;
+pub mod grid;
+pub mod registry;
+pub mod focus;
+pub mod animation;
+pub mod effects;
+pub mod svg;
+pub mod window;
+pub mod confirm;
+pub mod inspector;
+pub mod log_view;
+pub mod text;
+pub mod screen;
+pub mod redraw;
+pub mod menu_list;
+pub mod pause;
+pub mod title;
+pub mod pool_editor;
@@ -0,0 +1,163 @@
+//pause.rs
+use ratatui::layout::{Margin, Rect};
+
+use crate::components::enums::Menu;
+use crate::keymap::KeyAction;
+use crate::uihelp::menu_list::MenuList;
+
+/// How the game is played, shown on the rules page.
+pub const RULES: &str = "\
+The shotgun is loaded with a mix of live and blank shells. You are told how many of each, \
+but not in which order.
+
+On your turn, shoot the other player or yourself. A live shell takes one health. \
+A blank at yourself keeps the turn, anything else passes it on.
+
+Every load hands out items. Use them before you shoot: a saw doubles the damage, \
+a beer ejects the next shell, cigarettes heal, a magnifying glass shows the shell \
+in the chamber and handcuffs make the next player skip a turn.
+
+Special shells can show up too. Poison hurts over time, bean bags and tasers stun, \
+and an imposter looks like a blank but isn't.
+
+The last player standing wins.
+
+Classic plays with live and blank shells and the basic items. Chaos brings every shell \
+and every item, with more health to survive them. Double or Nothing is a string of short \
+stages: every stage you win adds to the pot and doubles the next prize, and you can cash \
+out after each one. Lose a stage and the pot is gone.";
+
+/// Entries of the first page of the pause menu.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PauseItem {
+    Resume,
+    Settings,
+    Save,
+    Rules,
+    QuitToTitle,
+}
+
+impl PauseItem {
+    pub const ALL: [PauseItem; 5] = [
+        PauseItem::Resume,
+        PauseItem::Settings,
+        PauseItem::Save,
+        PauseItem::Rules,
+        PauseItem::QuitToTitle,
+    ];
+
+    pub fn label(&self) -> &'static str {
+        match self {
+            PauseItem::Resume => "Resume",
+            PauseItem::Settings => "Settings",
+            PauseItem::Save => "Save",
+            PauseItem::Rules => "Rules",
+            PauseItem::QuitToTitle => "Quit to Title",
+        }
+    }
+
+    /// The page the entry opens, if it opens one.
+    fn page(&self) -> Option<Menu> {
+        match self {
+            PauseItem::Settings => Some(Menu::Settings),
+            PauseItem::Rules => Some(Menu::Rules),
+            _ => None,
+        }
+    }
+}
+
+/// Rows of the settings page.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SettingsRow {
+    Theme,
+    AnimationSpeed,
+    TickRate,
+    FrameRate,
+    Key(KeyAction),
+    Back,
+}
+
+impl SettingsRow {
+    pub fn all() -> Vec<SettingsRow> {
+        let mut rows = vec![SettingsRow::Theme, SettingsRow::AnimationSpeed, SettingsRow::TickRate, SettingsRow::FrameRate];
+        rows.extend(KeyAction::ALL.map(SettingsRow::Key));
+        rows.push(SettingsRow::Back);
+        rows
+    }
+}
+
+/// Moving around the pause menu.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum MenuAction {
+    Up,
+    Down,
+    /// change the selected setting
+    Left,
+    Right,
+    /// press the selected entry
+    Select,
+    /// a page back, or resume from the first page
+    Back,
+    /// a row under the mouse, true when it was clicked
+    Point(usize, bool),
+    /// the key pressed while waiting for a new binding
+    Bind(char),
+    /// a letter typed into the field being edited
+    Type(char),
+    /// takes the last letter off the field being edited
+    Erase,
+}
+
+/// Page, selection and scroll of the pause menu while it is open.
+#[derive(Debug, Clone, Default)]
+pub struct PauseMenu {
+    pub page: Menu,
+    pub selected: usize,
+    /// how far the rules are scrolled
+    pub scroll: usize,
+    /// the action waiting for a key
+    pub binding: Option<KeyAction>,
+    /// the outcome of the last save or binding, shown at the bottom
+    pub notice: Option<String>,
+    /// opened from the title screen, there is only the settings page
+    pub from_title: bool,
+}
+
+impl PauseMenu {
+    pub fn new() -> PauseMenu {
+        PauseMenu::default()
+    }
+
+    /// Just the settings page, for the title screen.
+    pub fn settings() -> PauseMenu {
+        PauseMenu { page: Menu::Settings, from_title: true, ..PauseMenu::default() }
+    }
+
+    pub fn open(&mut self, page: Menu) {
+        *self = PauseMenu { page, ..PauseMenu::default() };
+    }
+
+    /// Back to the first page, with the entry that led away selected.
+    pub fn back(&mut self) {
+        let from = self.page;
+        self.open(Menu::Pause);
+        self.selected = PauseItem::ALL.iter().position(|item| item.page() == Some(from)).unwrap_or(0);
+    }
+
+    /// How many rows can be selected on the current page.
+    pub fn rows(&self) -> usize {
+        match self.page {
+            Menu::Pause => PauseItem::ALL.len(),
+            Menu::Settings => SettingsRow::all().len(),
+            Menu::Rules => 0,
+        }
+    }
+
+    /// The rows of the current page inside the panel.
+    pub fn list(&self, area: Rect) -> MenuList {
+        //the border plus a blank line all around, and the bottom line for the notice
+        let inner = area.inner(Margin::new(2, 2));
+        let list = Rect { height: inner.height.saturating_sub(1), ..inner };
+        MenuList::new(list, self.selected, self.rows())
+    }
+}
@@ -0,0 +1,153 @@
+//pool_editor.rs
+use std::collections::BTreeMap;
+
+use crate::components::enums::ReloadAmount;
+use crate::components::pool::{ShellPool, ShellRule};
+use crate::components::shotgun::Shell;
+use crate::uihelp::title::MAX_NAME_LEN;
+
+/// Highest weight the editor goes up to.
+const MAX_WEIGHT: u32 = 99;
+
+/// Rows of the pool editor.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum PoolRow {
+    /// a known pool to start from, or a new one
+    Load,
+    Name,
+    GuaranteedBlank,
+    Shell(Shell),
+    Save,
+    Back,
+}
+
+impl PoolRow {
+    pub fn all() -> Vec<PoolRow> {
+        let mut rows = vec![PoolRow::Load, PoolRow::Name, PoolRow::GuaranteedBlank];
+        rows.extend(Shell::ALL.map(PoolRow::Shell));
+        rows.extend([PoolRow::Save, PoolRow::Back]);
+        rows
+    }
+}
+
+/// The number of a shell row that Left/Right change, Enter moves to the next one.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum ShellField {
+    #[default]
+    Weight,
+    Min,
+    Max,
+}
+
+impl ShellField {
+    pub const ALL: [ShellField; 3] = [ShellField::Weight, ShellField::Min, ShellField::Max];
+
+    pub fn label(&self) -> &'static str {
+        match self {
+            ShellField::Weight => "weight",
+            ShellField::Min => "min",
+            ShellField::Max => "max",
+        }
+    }
+}
+
+/// The pool being built in the editor.
+#[derive(Debug, Clone)]
+pub struct PoolEditor {
+    pub pool: ShellPool,
+    pub field: ShellField,
+    /// which known pool was loaded, `None` for a new one
+    pub base: Option<usize>,
+}
+
+impl Default for PoolEditor {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl PoolEditor {
+    /// A new pool of live and blank shells.
+    pub fn new() -> PoolEditor {
+        let shells = BTreeMap::from([(Shell::Live, ShellRule::new(10)), (Shell::Blank, ShellRule::new(14))]);
+        PoolEditor {
+            pool: ShellPool { id: String::new(), name: String::from("New pool"), guaranteed_blank: true, shells },
+            field: ShellField::Weight,
+            base: None,
+        }
+    }
+
+    /// Starts over from a known pool, saving it again replaces it.
+    pub fn load(&mut self, pool: &ShellPool, base: usize) {
+        self.pool = pool.clone();
+        self.base = Some(base);
+    }
+
+    /// The rule of a shell, shells the pool doesn't have never load.
+    pub fn rule(&self, shell: &Shell) -> ShellRule {
+        self.pool.shells.get(shell).copied().unwrap_or(ShellRule::new(0))
+    }
+
+    /// Steps the selected number of a shell up or down.
+    pub fn step(&mut self, shell: &Shell, forward: bool) {
+        let largest = ReloadAmount::Five.as_usize();
+        let mut rule = self.rule(shell);
+        match self.field {
+            ShellField::Weight if forward => rule.weight = (rule.weight + 1).min(MAX_WEIGHT),
+            ShellField::Weight => rule.weight = rule.weight.saturating_sub(1),
+            ShellField::Min if forward => rule.min = (rule.min + 1).min(largest),
+            ShellField::Min => rule.min = rule.min.saturating_sub(1),
+            //past the largest load there is no limit
+            ShellField::Max => {
+                rule.max = match (rule.max, forward) {
+                    (Some(max), true) if max >= largest => None,
+                    (Some(max), true) => Some(max + 1),
+                    (Some(max), false) => Some(max.saturating_sub(1)),
+                    (None, true) => None,
+                    (None, false) => Some(largest),
+                }
+            },
+        }
+        self.pool.shells.insert(shell.clone(), rule);
+    }
+
+    /// Moves to the next number of the shell rows.
+    pub fn next_field(&mut self) {
+        let idx = ShellField::ALL.iter().position(|field| *field == self.field).unwrap_or(0);
+        self.field = ShellField::ALL[(idx + 1) % ShellField::ALL.len()];
+    }
+
+    /// Types into the name. Renaming makes a new pool, the loaded one stays as it was.
+    pub fn type_name(&mut self, key: char) {
+        if self.pool.name.chars().count() < MAX_NAME_LEN {
+            self.pool.name.push(key);
+            self.pool.id.clear();
+        }
+    }
+
+    pub fn erase_name(&mut self) {
+        self.pool.name.pop();
+        self.pool.id.clear();
+    }
+
+    /// The pool as it is saved: a new pool gets its id from the name and shells that never load
+    /// are left out.
+    pub fn finished(&self) -> ShellPool {
+        let mut pool = self.pool.clone();
+        pool.name = pool.name.trim().to_string();
+        if pool.id.is_empty() {
+            pool.id = slug(&pool.name);
+        }
+        pool.shells.retain(|_, rule| rule.weight > 0 || rule.min > 0);
+        pool
+    }
+}
+
+/// Lowercase letters and digits of a name, joined by underscores.
+pub fn slug(name: &str) -> String {
+    name.split(|c: char| !c.is_alphanumeric())
+        .filter(|word| !word.is_empty())
+        .map(str::to_lowercase)
+        .collect::<Vec<_>>()
+        .join("_")
+}
@@ -0,0 +1,32 @@
+//redraw.rs
+
+/// Keeps track of whether the screen is out of date.
+///
+/// Anything that changes what is shown marks it, the main loop only draws when it is marked.
+/// Ticks where nothing happened leave it alone, so an idle game doesn't draw at all.
+#[derive(Debug)]
+pub struct Redraw {
+    dirty: bool,
+}
+
+impl Default for Redraw {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Redraw {
+    /// Starts out marked, so the first frame gets drawn.
+    pub fn new() -> Redraw {
+        Redraw { dirty: true }
+    }
+
+    pub fn request(&mut self) {
+        self.dirty = true;
+    }
+
+    /// Whether a draw is due, the request is used up by asking.
+    pub fn take(&mut self) -> bool {
+        std::mem::replace(&mut self.dirty, false)
+    }
+}
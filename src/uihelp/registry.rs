@@ -0,0 +1,559 @@
+//registry.rs
+use crossterm::event::{KeyCode, KeyEvent, KeyModifiers, MouseButton, MouseEvent, MouseEventKind};
+use ratatui::{layout::Rect, style::Color, widgets::{Block, Borders}, Frame};
+
+use crate::app::App;
+use crate::components::enums::ConfirmAction;
+use crate::event::AppEvent;
+use crate::uihelp::confirm::Confirm;
+use crate::uihelp::inspector::{self, Cheat, Editable, InspectorAction};
+use crate::keymap::KeyAction;
+use crate::uihelp::log_view::LogAction;
+use crate::uihelp::pause::MenuAction;
+use crate::ui;
+use crate::uihelp::layout::{Anchor, Placement, SizeRule};
+use crate::uihelp::screen::ScreenSize;
+use crate::uihelp::widget_data::WidgetKind;
+
+/// Draws a widget into the area the layout engine gave it.
+pub type RenderFn = fn(&App, &mut Frame, Rect);
+/// Handles a key while the widget is focused, returns whether the key was used.
+pub type KeyFn = fn(&mut App, &KeyEvent) -> bool;
+/// Handles a mouse event over the widget's area, returns whether the event was used.
+pub type MouseFn = fn(&mut App, &MouseEvent, Rect) -> bool;
+
+/// Everything the app needs to know about a widget.
+///
+/// Adding a panel means adding a [`WidgetKind`] and one entry to [`REGISTRY`].
+pub struct WidgetSpec {
+    pub kind: WidgetKind,
+    pub render: RenderFn,
+    pub placement: Placement,
+    /// placement on narrow terminals, `None` keeps the normal one
+    pub compact: Option<Placement>,
+    /// can Tab land on it
+    pub focusable: bool,
+    /// can be dragged and resized with the mouse
+    pub movable: bool,
+    /// takes all input until it is closed
+    pub modal: bool,
+    /// shown when the app starts
+    pub displayed: bool,
+    /// key that shows and hides it
+    pub hotkey: Option<char>,
+    pub on_key: Option<KeyFn>,
+    pub on_mouse: Option<MouseFn>,
+    pub color: Option<Color>,
+    pub content: Option<&'static str>,
+}
+
+/// Every widget, in focus order.
+pub static REGISTRY: [WidgetSpec; 9] = [
+    WidgetSpec {
+        kind: WidgetKind::Log,
+        render: ui::render_log_popup,
+        placement: Placement::floating(
+            Anchor::Right, SizeRule::percent(33).min(20), SizeRule::percent(75).min(6), 3,
+        ),
+        compact: Some(Placement::floating(
+            Anchor::Right, SizeRule::percent(50).min(20), SizeRule::percent(75).min(6), 3,
+        )),
+        focusable: true,
+        movable: true,
+        modal: false,
+        displayed: false,
+        hotkey: Some('l'),
+        on_key: Some(log_keys),
+        on_mouse: Some(log_mouse),
+        color: Some(Color::White),
+        content: None,
+    },
+    WidgetSpec {
+        kind: WidgetKind::Data,
+        render: ui::render_data_popup,
+        placement: Placement::floating(
+            Anchor::TopLeft, SizeRule::percent(40).min(30), SizeRule::percent(60).min(8), 2,
+        ),
+        compact: Some(Placement::floating(
+            Anchor::TopLeft, SizeRule::percent(60).min(30), SizeRule::percent(60).min(8), 2,
+        )),
+        focusable: true,
+        movable: true,
+        modal: false,
+        displayed: false,
+        hotkey: Some('d'),
+        on_key: Some(data_keys),
+        on_mouse: Some(data_mouse),
+        color: Some(Color::Green),
+        content: None,
+    },
+    WidgetSpec {
+        kind: WidgetKind::Inventory,
+        render: ui::render_inventory_popup,
+        placement: Placement::docked(
+            Anchor::Bottom, SizeRule::fill(), SizeRule::percent(25).min(6).max(9), 1,
+        ),
+        compact: None,
+        focusable: true,
+        movable: false,
+        modal: false,
+        displayed: false,
+        hotkey: Some('i'),
+        on_key: Some(inventory_keys),
+        on_mouse: Some(inventory_mouse),
+        color: Some(Color::White),
+        content: None,
+    },
+    WidgetSpec {
+        kind: WidgetKind::Player,
+        render: ui::render_player_popup,
+        placement: Placement::docked(
+            Anchor::Left, SizeRule::percent(20).min(12).max(30), SizeRule::fill(), 1,
+        ),
+        compact: Some(Placement::docked(
+            Anchor::Top, SizeRule::fill(), SizeRule::fixed(7), 1,
+        )),
+        focusable: true,
+        movable: false,
+        modal: false,
+        displayed: false,
+        hotkey: Some('p'),
+        on_key: None,
+        on_mouse: Some(player_mouse),
+        color: Some(Color::White),
+        content: None,
+    },
+    WidgetSpec {
+        kind: WidgetKind::Shotgun,
+        render: ui::render_shotgun_popup,
+        placement: Placement::floating(
+            Anchor::Center, SizeRule::fixed(106), SizeRule::fixed(12), 0,
+        ),
+        compact: None,
+        focusable: true,
+        movable: false,
+        modal: false,
+        displayed: true,
+        hotkey: None,
+        on_key: Some(shotgun_keys),
+        on_mouse: Some(shotgun_mouse),
+        color: Some(Color::White),
+        //the art comes from the asset pack of the current model
+        content: None,
+    },
+    WidgetSpec {
+        kind: WidgetKind::Confirm,
+        render: ui::render_confirm_popup,
+        placement: Placement::floating(
+            Anchor::Center, SizeRule::fixed(44), SizeRule::fixed(9), 9,
+        ),
+        compact: None,
+        focusable: true,
+        movable: false,
+        modal: true,
+        displayed: false,
+        hotkey: None,
+        on_key: Some(confirm_keys),
+        on_mouse: Some(confirm_mouse),
+        color: Some(Color::Yellow),
+        content: None,
+    },
+    WidgetSpec {
+        kind: WidgetKind::Clock,
+        render: ui::render_clock_popup,
+        placement: Placement::floating(
+            Anchor::TopRight, SizeRule::fixed(24), SizeRule::fixed(3), 2,
+        ),
+        compact: None,
+        focusable: false,
+        movable: false,
+        modal: false,
+        displayed: false,
+        hotkey: Some('t'),
+        on_key: None,
+        on_mouse: None,
+        color: Some(Color::White),
+        content: None,
+    },
+    WidgetSpec {
+        kind: WidgetKind::Pause,
+        render: ui::render_pause_popup,
+        placement: Placement::floating(
+            Anchor::Center, SizeRule::fixed(48), SizeRule::fixed(17), 8,
+        ),
+        compact: None,
+        focusable: true,
+        movable: false,
+        modal: true,
+        displayed: false,
+        hotkey: None,
+        on_key: Some(pause_keys),
+        on_mouse: Some(pause_mouse),
+        color: Some(Color::White),
+        content: None,
+    },
+    WidgetSpec {
+        kind: WidgetKind::Title,
+        render: ui::render_title_popup,
+        //takes the whole screen, the table isn't drawn under it
+        placement: Placement::floating(
+            Anchor::Center, SizeRule::fill(), SizeRule::fill(), 7,
+        ),
+        compact: None,
+        focusable: true,
+        movable: false,
+        modal: true,
+        displayed: false,
+        hotkey: None,
+        on_key: Some(title_keys),
+        on_mouse: Some(title_mouse),
+        color: Some(Color::White),
+        content: None,
+    },
+];
+
+impl WidgetSpec {
+    /// Where the widget goes on a screen of this size.
+    pub fn placement_for(&self, screen: ScreenSize) -> Placement {
+        match (screen, self.compact) {
+            (ScreenSize::Compact, Some(compact)) => compact,
+            _ => self.placement,
+        }
+    }
+}
+
+pub fn spec(kind: WidgetKind) -> &'static WidgetSpec {
+    REGISTRY
+        .iter()
+        .find(|spec| spec.kind == kind)
+        .expect("every widget kind is registered")
+}
+
+/// Finds the widget that is toggled by a key, ignoring case.
+pub fn by_hotkey(key: char) -> Option<&'static WidgetSpec> {
+    REGISTRY
+        .iter()
+        .find(|spec| spec.hotkey.is_some_and(|hotkey| hotkey.eq_ignore_ascii_case(&key)))
+}
+
+/// j/k and the page keys scroll, '/' searches and n/N jump between matches.
+fn log_keys(app: &mut App, key_event: &KeyEvent) -> bool {
+    //while typing a search every key goes into it
+    if app.log_view.searching {
+        let action = match key_event.code {
+            KeyCode::Char('c') if key_event.modifiers == KeyModifiers::CONTROL => return false,
+            KeyCode::Char(c) => LogAction::SearchInput(c),
+            KeyCode::Backspace => LogAction::SearchBackspace,
+            KeyCode::Enter => LogAction::SearchDone,
+            KeyCode::Esc => LogAction::SearchCancel,
+            _ => return true,
+        };
+        app.events.send(AppEvent::Log(action));
+        return true;
+    }
+
+    let action = match key_event.code {
+        KeyCode::Char('k') | KeyCode::Up => LogAction::Up,
+        KeyCode::Char('j') | KeyCode::Down => LogAction::Down,
+        KeyCode::PageUp => LogAction::PageUp,
+        KeyCode::PageDown => LogAction::PageDown,
+        KeyCode::Home | KeyCode::Char('g') => LogAction::Home,
+        KeyCode::End | KeyCode::Char('G') => LogAction::End,
+        KeyCode::Char('/') => LogAction::Search,
+        KeyCode::Char('n') if !app.log_view.query.is_empty() => LogAction::NextMatch,
+        KeyCode::Char('N') if !app.log_view.query.is_empty() => LogAction::PrevMatch,
+        _ => return false,
+    };
+    app.events.send(AppEvent::Log(action));
+    true
+}
+
+/// Arrows or j/k move, Enter opens and closes, with cheats +/- and J/K edit the selected value.
+fn data_keys(app: &mut App, key_event: &KeyEvent) -> bool {
+    let action = match key_event.code {
+        KeyCode::Up | KeyCode::Char('k') => InspectorAction::Up,
+        KeyCode::Down | KeyCode::Char('j') => InspectorAction::Down,
+        KeyCode::PageUp => InspectorAction::PageUp,
+        KeyCode::PageDown => InspectorAction::PageDown,
+        KeyCode::Enter | KeyCode::Right | KeyCode::Char(' ') => InspectorAction::Toggle,
+        KeyCode::Left => InspectorAction::Collapse,
+        code if app.settings.cheats => {
+            let rows = app.inspector.rows(&inspector::tree(app));
+            let cheat = match (rows.get(app.inspector.selected).and_then(|row| row.edit), code) {
+                (Some(Editable::Shell(pos)), KeyCode::Char('+' | '=')) => Cheat::CycleShell(pos, true),
+                (Some(Editable::Shell(pos)), KeyCode::Char('-')) => Cheat::CycleShell(pos, false),
+                (Some(Editable::Shell(pos)), KeyCode::Char('K')) => Cheat::MoveShell(pos, false),
+                (Some(Editable::Shell(pos)), KeyCode::Char('J')) => Cheat::MoveShell(pos, true),
+                (Some(Editable::Health(idx)), KeyCode::Char('+' | '=')) => Cheat::Health(idx, true),
+                (Some(Editable::Health(idx)), KeyCode::Char('-')) => Cheat::Health(idx, false),
+                _ => return false,
+            };
+            app.events.send(AppEvent::Cheat(cheat));
+            return true;
+        },
+        _ => return false,
+    };
+    app.events.send(AppEvent::Inspect(action));
+    true
+}
+
+fn data_mouse(app: &mut App, mouse_event: &MouseEvent, area: Rect) -> bool {
+    let action = match mouse_event.kind {
+        MouseEventKind::ScrollUp => InspectorAction::Up,
+        MouseEventKind::ScrollDown => InspectorAction::Down,
+        MouseEventKind::Down(MouseButton::Left) => {
+            let inner = Block::default().borders(Borders::ALL).inner(area);
+            if !inner.contains((mouse_event.column, mouse_event.row).into()) {
+                return false;
+            }
+            //the same first row the panel was drawn from
+            let len = app.inspector.rows(&inspector::tree(app)).len();
+            let row = app.inspector.top(len, inner.height) + (mouse_event.row - inner.y) as usize;
+            app.events.send(AppEvent::FocusWidget(WidgetKind::Data));
+            InspectorAction::Click(row)
+        },
+        _ => return false,
+    };
+    app.events.send(AppEvent::Inspect(action));
+    true
+}
+
+/// Number keys pick a slot directly, arrows move through them, Enter uses the item.
+fn inventory_keys(app: &mut App, key_event: &KeyEvent) -> bool {
+    let selected = app.inventory_selected;
+    match key_event.code {
+        KeyCode::Char(digit @ '1'..='9') => {
+            let slot = digit as usize - '1' as usize;
+            app.events.send(AppEvent::SelectItem(slot))
+        },
+        KeyCode::Left => app.events.send(AppEvent::SelectItem(selected.saturating_sub(1))),
+        KeyCode::Right => app.events.send(AppEvent::SelectItem(selected + 1)),
+        KeyCode::Enter => app.events.send(AppEvent::UseItem(selected)),
+        _ => return false,
+    }
+    true
+}
+
+fn shotgun_keys(app: &mut App, key_event: &KeyEvent) -> bool {
+    let KeyCode::Char(key) = key_event.code else {
+        return false;
+    };
+    match app.settings.preferences.keymap.action(key) {
+        Some(KeyAction::Shoot) => app.events.send(AppEvent::Shoot),
+        Some(KeyAction::ShootSelf) => app.events.send(AppEvent::Ask(ConfirmAction::ShootSelf)),
+        _ => return false,
+    }
+    true
+}
+
+fn log_mouse(app: &mut App, mouse_event: &MouseEvent, _area: Rect) -> bool {
+    match mouse_event.kind {
+        MouseEventKind::ScrollUp => app.events.send(AppEvent::Log(LogAction::Up)),
+        MouseEventKind::ScrollDown => app.events.send(AppEvent::Log(LogAction::Down)),
+        _ => return false,
+    }
+    true
+}
+
+fn inventory_mouse(app: &mut App, mouse_event: &MouseEvent, area: Rect) -> bool {
+    let MouseEventKind::Down(MouseButton::Left) = mouse_event.kind else {
+        return false;
+    };
+    let (slots, _) = ui::inventory_layout(Block::default().borders(Borders::ALL).inner(area));
+    match ui::inventory_grid(app).cell_at(slots, (mouse_event.column, mouse_event.row).into()) {
+        //clicking the item that is already picked uses it
+        Some(slot) if slot == app.inventory_selected && app.widget_data.is_focused(WidgetKind::Inventory) => {
+            app.events.send(AppEvent::UseItem(slot));
+            true
+        },
+        Some(slot) => {
+            app.events.send(AppEvent::SelectItem(slot));
+            app.events.send(AppEvent::FocusWidget(WidgetKind::Inventory));
+            true
+        },
+        None => false,
+    }
+}
+
+/// Clicking a player aims at them.
+fn player_mouse(app: &mut App, mouse_event: &MouseEvent, area: Rect) -> bool {
+    let MouseEventKind::Down(MouseButton::Left) = mouse_event.kind else {
+        return false;
+    };
+    match ui::player_grid(app).cell_at(area, (mouse_event.column, mouse_event.row).into()) {
+        Some(idx) => {
+            app.events.send(AppEvent::SelectTarget(idx));
+            true
+        },
+        None => false,
+    }
+}
+
+/// Clicking the shotgun once focuses it, clicking it again fires.
+fn shotgun_mouse(app: &mut App, mouse_event: &MouseEvent, _area: Rect) -> bool {
+    match mouse_event.kind {
+        MouseEventKind::Down(MouseButton::Left) if app.widget_data.is_focused(WidgetKind::Shotgun) => {
+            app.events.send(AppEvent::Shoot)
+        },
+        _ => return false,
+    }
+    true
+}
+
+/// Arrows pick a button and Enter presses it, y and n answer straight away.
+fn confirm_keys(app: &mut App, key_event: &KeyEvent) -> bool {
+    let Some(confirm) = app.confirm.as_mut() else {
+        return false;
+    };
+    let action = confirm.action;
+    match key_event.code {
+        KeyCode::Left | KeyCode::Right | KeyCode::Tab | KeyCode::BackTab
+        | KeyCode::Char('h') | KeyCode::Char('l') => confirm.toggle(),
+        KeyCode::Enter | KeyCode::Char(' ') => {
+            let accepted = confirm.yes_selected;
+            app.events.send(AppEvent::Answer(action, accepted))
+        },
+        KeyCode::Char('y' | 'Y') => app.events.send(AppEvent::Answer(action, true)),
+        KeyCode::Char('n' | 'N') | KeyCode::Esc => app.events.send(AppEvent::Answer(action, false)),
+        _ => return false,
+    }
+    true
+}
+
+/// Hovering a button selects it, clicking presses it.
+fn confirm_mouse(app: &mut App, mouse_event: &MouseEvent, area: Rect) -> bool {
+    let Some(confirm) = app.confirm.as_mut() else {
+        return false;
+    };
+    let (_, yes, no) = Confirm::layout(area);
+    let position = (mouse_event.column, mouse_event.row).into();
+    let button = if yes.contains(position) {
+        Some(true)
+    } else if no.contains(position) {
+        Some(false)
+    } else {
+        None
+    };
+
+    match (mouse_event.kind, button) {
+        (MouseEventKind::Moved, Some(accepted)) => {
+            if confirm.yes_selected != accepted {
+                confirm.yes_selected = accepted;
+                //plain mouse moves don't redraw on their own
+                app.redraw.request();
+            }
+        },
+        (MouseEventKind::Down(MouseButton::Left), Some(accepted)) => {
+            let action = confirm.action;
+            app.events.send(AppEvent::Answer(action, accepted))
+        },
+        //clicks on the message do nothing, but are still kept from the panels below
+        (MouseEventKind::Down(_), None) => {},
+        _ => return false,
+    }
+    true
+}
+
+/// Arrows or j/k move, Enter picks, Left/Right change a setting and Esc goes back a page.
+fn pause_keys(app: &mut App, key_event: &KeyEvent) -> bool {
+    let Some(menu) = app.pause.as_ref() else {
+        return false;
+    };
+    if key_event.code == KeyCode::Char('c') && key_event.modifiers == KeyModifiers::CONTROL {
+        return false;
+    }
+    //waiting for a new binding, any other key cancels
+    let action = if menu.binding.is_some() {
+        match key_event.code {
+            KeyCode::Char(key) => MenuAction::Bind(key),
+            _ => MenuAction::Back,
+        }
+    } else {
+        match key_event.code {
+            KeyCode::Up | KeyCode::Char('k') => MenuAction::Up,
+            KeyCode::Down | KeyCode::Char('j') => MenuAction::Down,
+            KeyCode::Left | KeyCode::Char('h') => MenuAction::Left,
+            KeyCode::Right | KeyCode::Char('l') => MenuAction::Right,
+            KeyCode::Enter | KeyCode::Char(' ') => MenuAction::Select,
+            KeyCode::Esc | KeyCode::Backspace => MenuAction::Back,
+            //the pause key closes the menu again
+            KeyCode::Char(key) if app.settings.preferences.keymap.action(key) == Some(KeyAction::Pause) => {
+                app.events.send(AppEvent::Resume);
+                return true;
+            },
+            //nothing below the menu gets keys while it is open
+            _ => return true,
+        }
+    };
+    app.events.send(AppEvent::Menu(action));
+    true
+}
+
+/// Hovering an entry selects it, clicking presses it and the wheel scrolls.
+fn pause_mouse(app: &mut App, mouse_event: &MouseEvent, area: Rect) -> bool {
+    let Some(menu) = app.pause.as_ref() else {
+        return false;
+    };
+    let row = menu.list(area).row_at(mouse_event.column, mouse_event.row);
+    let action = match (mouse_event.kind, row) {
+        (MouseEventKind::Moved, Some(row)) if row != menu.selected => MenuAction::Point(row, false),
+        (MouseEventKind::Down(MouseButton::Left), Some(row)) => MenuAction::Point(row, true),
+        (MouseEventKind::ScrollUp, _) => MenuAction::Up,
+        (MouseEventKind::ScrollDown, _) => MenuAction::Down,
+        (MouseEventKind::Down(_), None) => return true,
+        _ => return false,
+    };
+    app.events.send(AppEvent::Menu(action));
+    true
+}
+
+/// Arrows or j/k move, Enter picks, Left/Right change an option and typing fills in a name or the seed.
+fn title_keys(app: &mut App, key_event: &KeyEvent) -> bool {
+    let Some(title) = app.title.as_ref() else {
+        return false;
+    };
+    if key_event.code == KeyCode::Char('c') && key_event.modifiers == KeyModifiers::CONTROL {
+        return false;
+    }
+    let action = if title.editing {
+        match key_event.code {
+            KeyCode::Char(key) => MenuAction::Type(key),
+            KeyCode::Backspace => MenuAction::Erase,
+            KeyCode::Enter | KeyCode::Esc => MenuAction::Select,
+            _ => return true,
+        }
+    } else {
+        match key_event.code {
+            KeyCode::Up | KeyCode::Char('k') => MenuAction::Up,
+            KeyCode::Down | KeyCode::Char('j') => MenuAction::Down,
+            KeyCode::Left | KeyCode::Char('h') => MenuAction::Left,
+            KeyCode::Right | KeyCode::Char('l') => MenuAction::Right,
+            KeyCode::Enter | KeyCode::Char(' ') => MenuAction::Select,
+            KeyCode::Esc | KeyCode::Backspace => MenuAction::Back,
+            //the table behind the title screen doesn't get keys
+            _ => return true,
+        }
+    };
+    app.events.send(AppEvent::Title(action));
+    true
+}
+
+/// Hovering an entry selects it, clicking presses it and the wheel moves the selection.
+fn title_mouse(app: &mut App, mouse_event: &MouseEvent, area: Rect) -> bool {
+    let Some(title) = app.title.as_ref() else {
+        return false;
+    };
+    //clicking away from a name being typed finishes it
+    let row = title.list(area).row_at(mouse_event.column, mouse_event.row).filter(|_| !title.editing);
+    let action = match (mouse_event.kind, row) {
+        (MouseEventKind::Moved, Some(row)) if row != title.selected => MenuAction::Point(row, false),
+        (MouseEventKind::Down(MouseButton::Left), Some(row)) => MenuAction::Point(row, true),
+        (MouseEventKind::Down(MouseButton::Left), None) if title.editing => MenuAction::Select,
+        (MouseEventKind::ScrollUp, _) if !title.editing => MenuAction::Up,
+        (MouseEventKind::ScrollDown, _) if !title.editing => MenuAction::Down,
+        (MouseEventKind::Down(_), _) => return true,
+        _ => return false,
+    };
+    app.events.send(AppEvent::Title(action));
+    true
+}
@@ -0,0 +1,36 @@
+//screen.rs
+use ratatui::layout::Rect;
+
+/// Smallest terminal the game is drawn in, anything smaller gets a notice instead.
+pub const MIN_WIDTH: u16 = 60;
+pub const MIN_HEIGHT: u16 = 20;
+/// Narrower than this and panels switch to their compact placement, it is about where the
+/// full shotgun art stops fitting.
+pub const COMPACT_WIDTH: u16 = 110;
+
+/// How much room the terminal gives the game.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum ScreenSize {
+    /// below the minimum, nothing but the notice is drawn
+    TooSmall,
+    /// narrow, panels stack instead of sitting side by side
+    Compact,
+    #[default]
+    Full,
+}
+
+impl ScreenSize {
+    pub fn of(width: u16, height: u16) -> ScreenSize {
+        if width < MIN_WIDTH || height < MIN_HEIGHT {
+            ScreenSize::TooSmall
+        } else if width < COMPACT_WIDTH {
+            ScreenSize::Compact
+        } else {
+            ScreenSize::Full
+        }
+    }
+
+    pub fn of_area(area: Rect) -> ScreenSize {
+        Self::of(area.width, area.height)
+    }
+}
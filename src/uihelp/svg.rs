@@ -0,0 +1,227 @@
+//svg.rs
+use color_eyre::eyre::WrapErr;
+use ratatui::{
+    prelude::*,
+    symbols::Marker,
+    widgets::canvas::{Canvas, Line as CanvasLine, Points},
+};
+use usvg::{NodeExt, NodeKind, PathSegment, TreeParsing};
+
+/// How many straight lines a bezier curve is cut into.
+const CURVE_STEPS: usize = 8;
+
+/// One flattened svg path, in viewbox coordinates.
+#[derive(Debug, Clone)]
+struct Shape {
+    //each polygon is a closed or open run of points
+    polygons: Vec<Vec<(f64, f64)>>,
+    fill: Option<(Color, bool)>,
+    stroke: Option<Color>,
+}
+
+/// Vector art parsed from an svg and flattened into lines, ready to draw on a [`Canvas`].
+///
+/// Parsing is done once, the art is rasterized again on every render so it scales with its area.
+#[derive(Debug, Clone)]
+pub struct SvgArt {
+    shapes: Vec<Shape>,
+    width: f64,
+    height: f64,
+}
+
+impl SvgArt {
+    pub fn parse(data: &str) -> color_eyre::Result<SvgArt> {
+        let tree = usvg::Tree::from_str(data, &usvg::Options::default())
+            .wrap_err("could not parse svg")?;
+        let view_box = tree.view_box.rect;
+        let (left, top) = (view_box.x(), view_box.y());
+
+        let mut shapes = Vec::new();
+        for node in tree.root.descendants() {
+            let NodeKind::Path(ref path) = *node.borrow() else {
+                continue;
+            };
+            let transform = node.abs_transform();
+            let point = |x: f64, y: f64| {
+                let (x, y) = transform.apply(x, y);
+                (x - left, y - top)
+            };
+
+            let mut polygons: Vec<Vec<(f64, f64)>> = Vec::new();
+            let mut current: Vec<(f64, f64)> = Vec::new();
+            for segment in path.data.segments() {
+                match segment {
+                    PathSegment::MoveTo { x, y } => {
+                        if current.len() > 1 {
+                            polygons.push(std::mem::take(&mut current));
+                        }
+                        current = vec![point(x, y)];
+                    },
+                    PathSegment::LineTo { x, y } => current.push(point(x, y)),
+                    PathSegment::CurveTo { x1, y1, x2, y2, x, y } => {
+                        let start = current.last().copied().unwrap_or((0.0, 0.0));
+                        let (c1, c2, end) = (point(x1, y1), point(x2, y2), point(x, y));
+                        for step in 1..=CURVE_STEPS {
+                            current.push(cubic(start, c1, c2, end, step as f64 / CURVE_STEPS as f64));
+                        }
+                    },
+                    PathSegment::ClosePath => {
+                        if let Some(first) = current.first().copied() {
+                            current.push(first);
+                        }
+                    },
+                }
+            }
+            if current.len() > 1 {
+                polygons.push(current);
+            }
+
+            shapes.push(Shape {
+                polygons,
+                fill: path.fill.as_ref().and_then(|fill| {
+                    let even_odd = matches!(fill.rule, usvg::FillRule::EvenOdd);
+                    paint_color(&fill.paint).map(|color| (color, even_odd))
+                }),
+                stroke: path.stroke.as_ref().and_then(|stroke| paint_color(&stroke.paint)),
+            });
+        }
+
+        Ok(SvgArt { shapes, width: view_box.width(), height: view_box.height() })
+    }
+
+    /// A widget that draws the art scaled to fit its area, keeping the aspect ratio.
+    pub fn widget(&self) -> SvgWidget<'_> {
+        SvgWidget { art: self, marker: Marker::Braille, color: None }
+    }
+}
+
+/// Draws [`SvgArt`] with braille dots or half blocks.
+pub struct SvgWidget<'a> {
+    art: &'a SvgArt,
+    marker: Marker,
+    color: Option<Color>,
+}
+
+impl SvgWidget<'_> {
+    /// `Marker::Braille` and `Marker::HalfBlock` keep the art's proportions, others stretch it.
+    pub fn marker(mut self, marker: Marker) -> Self {
+        self.marker = marker;
+        self
+    }
+
+    /// Draws everything in one color instead of the svg's own.
+    pub fn color(mut self, color: Option<Color>) -> Self {
+        self.color = color;
+        self
+    }
+}
+
+impl Widget for SvgWidget<'_> {
+    fn render(self, area: Rect, buf: &mut Buffer) {
+        if area.is_empty() || self.art.width <= 0.0 || self.art.height <= 0.0 {
+            return;
+        }
+
+        //how many dots fit in a cell, dots are roughly square for braille and half blocks
+        let (dots_x, dots_y) = match self.marker {
+            Marker::Braille => (2.0, 4.0),
+            Marker::HalfBlock => (1.0, 2.0),
+            _ => (1.0, 1.0),
+        };
+        let dots_w = area.width as f64 * dots_x;
+        let dots_h = area.height as f64 * dots_y;
+
+        //grow the bounds on one axis so the art is centered and not stretched
+        let scale = (dots_w / self.art.width).min(dots_h / self.art.height);
+        let bounds_w = dots_w / scale;
+        let bounds_h = dots_h / scale;
+        let pad_x = (bounds_w - self.art.width) / 2.0;
+        let pad_y = (bounds_h - self.art.height) / 2.0;
+        //one dot, in viewbox units
+        let step = 1.0 / scale;
+
+        let art = self.art;
+        let recolor = self.color;
+        Canvas::default()
+            .marker(self.marker)
+            .x_bounds([-pad_x, art.width + pad_x])
+            .y_bounds([-pad_y, art.height + pad_y])
+            .paint(move |ctx| {
+                for shape in &art.shapes {
+                    if let Some((color, even_odd)) = shape.fill {
+                        let coords = fill_points(&shape.polygons, even_odd, step, art);
+                        ctx.draw(&Points { coords: &coords, color: recolor.unwrap_or(color) });
+                    }
+                    if let Some(color) = shape.stroke {
+                        for polygon in &shape.polygons {
+                            for pair in polygon.windows(2) {
+                                //canvas y grows up, svg y grows down
+                                ctx.draw(&CanvasLine {
+                                    x1: pair[0].0,
+                                    y1: art.height - pair[0].1,
+                                    x2: pair[1].0,
+                                    y2: art.height - pair[1].1,
+                                    color: recolor.unwrap_or(color),
+                                });
+                            }
+                        }
+                    }
+                }
+            })
+            .render(area, buf);
+    }
+}
+
+/// Samples the inside of the polygons once per dot.
+fn fill_points(polygons: &[Vec<(f64, f64)>], even_odd: bool, step: f64, art: &SvgArt) -> Vec<(f64, f64)> {
+    let mut coords = Vec::new();
+    let mut y = step / 2.0;
+    while y < art.height {
+        let mut x = step / 2.0;
+        while x < art.width {
+            let winding = winding(polygons, x, y);
+            let inside = if even_odd { winding % 2 != 0 } else { winding != 0 };
+            if inside {
+                coords.push((x, art.height - y));
+            }
+            x += step;
+        }
+        y += step;
+    }
+    coords
+}
+
+/// Winding number of the point against every edge, polygons are closed implicitly.
+fn winding(polygons: &[Vec<(f64, f64)>], x: f64, y: f64) -> i32 {
+    let mut winding = 0;
+    for polygon in polygons {
+        let closing = [polygon[polygon.len() - 1], polygon[0]];
+        for edge in polygon.windows(2).chain(std::iter::once(&closing[..])) {
+            let ((x1, y1), (x2, y2)) = (edge[0], edge[1]);
+            if (y1 <= y) != (y2 <= y) {
+                let cross_x = x1 + (y - y1) / (y2 - y1) * (x2 - x1);
+                if cross_x > x {
+                    winding += if y2 > y1 { 1 } else { -1 };
+                }
+            }
+        }
+    }
+    winding
+}
+
+fn cubic(p0: (f64, f64), p1: (f64, f64), p2: (f64, f64), p3: (f64, f64), t: f64) -> (f64, f64) {
+    let u = 1.0 - t;
+    let (a, b, c, d) = (u * u * u, 3.0 * u * u * t, 3.0 * u * t * t, t * t * t);
+    (
+        a * p0.0 + b * p1.0 + c * p2.0 + d * p3.0,
+        a * p0.1 + b * p1.1 + c * p2.1 + d * p3.1,
+    )
+}
+
+/// Gradients and patterns have no single color, they are skipped.
+fn paint_color(paint: &usvg::Paint) -> Option<Color> {
+    match paint {
+        usvg::Paint::Color(color) => Some(Color::Rgb(color.red, color.green, color.blue)),
+        _ => None,
+    }
+}
@@ -0,0 +1,21 @@
+//text.rs
+use ratatui::{
+    text::Text,
+    widgets::{Paragraph, Wrap},
+};
+
+/// Rows `text` takes up when a wrapping [`Paragraph`] draws it `width` cells wide.
+///
+/// This asks ratatui's own wrapper, so emoji and other wide glyphs count as two cells and the
+/// result always agrees with what ends up on screen.
+pub fn wrapped_height<'a>(text: impl Into<Text<'a>>, width: u16) -> usize {
+    if width == 0 {
+        return 0;
+    }
+    Paragraph::new(text).wrap(Wrap { trim: false }).line_count(width)
+}
+
+/// The furthest a view of `height` rows can scroll through `content` rows.
+pub fn max_scroll(content: usize, height: u16) -> usize {
+    content.saturating_sub(height as usize)
+}
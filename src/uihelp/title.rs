@@ -0,0 +1,163 @@
+//title.rs
+use ratatui::layout::{Constraint, Flex, Layout, Rect};
+
+use crate::components::enums::TitlePage;
+use crate::components::setup::GameSetup;
+use crate::uihelp::menu_list::MenuList;
+use crate::uihelp::pool_editor::{PoolEditor, PoolRow};
+
+/// Longest name a player can type in.
+pub const MAX_NAME_LEN: usize = 16;
+/// How wide the menu is, the rest of the screen is left empty.
+const LIST_WIDTH: u16 = 44;
+
+/// Entries of the title screen.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TitleItem {
+    NewGame,
+    Continue,
+    Multiplayer,
+    Replays,
+    ShellPools,
+    Settings,
+    Quit,
+}
+
+impl TitleItem {
+    pub const ALL: [TitleItem; 7] = [
+        TitleItem::NewGame,
+        TitleItem::Continue,
+        TitleItem::Multiplayer,
+        TitleItem::Replays,
+        TitleItem::ShellPools,
+        TitleItem::Settings,
+        TitleItem::Quit,
+    ];
+
+    pub fn label(&self) -> &'static str {
+        match self {
+            TitleItem::NewGame => "New Game",
+            TitleItem::Continue => "Continue",
+            TitleItem::Multiplayer => "Multiplayer",
+            TitleItem::Replays => "Replays",
+            TitleItem::ShellPools => "Shell Pools",
+            TitleItem::Settings => "Settings",
+            TitleItem::Quit => "Quit",
+        }
+    }
+}
+
+/// Rows of the New Game wizard.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SetupRow {
+    Mode,
+    Players,
+    /// name and whether the computer plays it
+    Seat(usize),
+    Health,
+    Shells,
+    Items,
+    Seed,
+    Start,
+    Back,
+}
+
+impl SetupRow {
+    pub fn all(setup: &GameSetup) -> Vec<SetupRow> {
+        let mut rows = vec![SetupRow::Mode, SetupRow::Players];
+        rows.extend((0..setup.seats.len()).map(SetupRow::Seat));
+        rows.extend([
+            SetupRow::Health,
+            SetupRow::Shells,
+            SetupRow::Items,
+            SetupRow::Seed,
+            SetupRow::Start,
+            SetupRow::Back,
+        ]);
+        rows
+    }
+}
+
+/// Rows of the Replays page.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ReplayRow {
+    Seed,
+    Play,
+    Back,
+}
+
+impl ReplayRow {
+    pub const ALL: [ReplayRow; 3] = [ReplayRow::Seed, ReplayRow::Play, ReplayRow::Back];
+}
+
+/// Page, selection and picks of the title screen while it is open.
+#[derive(Debug, Clone)]
+pub struct TitleMenu {
+    pub page: TitlePage,
+    pub selected: usize,
+    /// what the New Game wizard will start
+    pub setup: GameSetup,
+    /// the pool being built on the Shell Pools page
+    pub editor: PoolEditor,
+    /// the match the Replays page plays again, with the New Game picks
+    pub replay_seed: Option<u64>,
+    /// typing into the selected name or the seed
+    pub editing: bool,
+    /// there is a save for Continue to load
+    pub has_save: bool,
+    /// why the last entry didn't do anything, shown at the bottom
+    pub notice: Option<String>,
+}
+
+impl TitleMenu {
+    /// The wizard starts from `setup`, so the last picks are kept.
+    pub fn new(setup: GameSetup, has_save: bool) -> TitleMenu {
+        TitleMenu {
+            page: TitlePage::Main,
+            selected: 0,
+            setup,
+            editor: PoolEditor::new(),
+            replay_seed: None,
+            editing: false,
+            has_save,
+            notice: None,
+        }
+    }
+
+    pub fn open(&mut self, page: TitlePage) {
+        self.page = page;
+        self.selected = 0;
+        self.editing = false;
+        self.notice = None;
+    }
+
+    /// Back to the first page, with the New Game entry selected.
+    pub fn back(&mut self) {
+        self.open(TitlePage::Main);
+    }
+
+    /// How many rows can be selected on the current page.
+    pub fn rows(&self) -> usize {
+        match self.page {
+            TitlePage::Main => TitleItem::ALL.len(),
+            TitlePage::NewGame => SetupRow::all(&self.setup).len(),
+            TitlePage::PoolEditor => PoolRow::all().len(),
+            TitlePage::Replays => ReplayRow::ALL.len(),
+        }
+    }
+
+    /// Splits the screen into the banner, the rows and the notice line.
+    pub fn layout(area: Rect) -> (Rect, Rect, Rect) {
+        let [banner, list, notice] = Layout::vertical([Constraint::Length(3), Constraint::Fill(1), Constraint::Length(1)])
+            .margin(1)
+            .areas(area);
+        let [list] = Layout::horizontal([Constraint::Length(LIST_WIDTH)]).flex(Flex::Center).areas(list);
+        (banner, list, notice)
+    }
+
+    /// The rows of the current page.
+    pub fn list(&self, area: Rect) -> MenuList {
+        let (_, list, _) = Self::layout(area);
+        MenuList::new(list, self.selected, self.rows())
+    }
+}
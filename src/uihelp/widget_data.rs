@@ -1,318 +1,224 @@
 //widget-data.rs
-use std::cell::{ Ref, RefCell };
+use std::cell::{ Cell, Ref, RefCell };
+use std::collections::HashMap;
 
 use ratatui::layout::Rect;
+use ratatui::style::Color;
+use serde::{Deserialize, Serialize};
 
-use crate::ui::{SHOTGUN_ART, BANG, CLICK};
+use crate::uihelp::focus::FocusManager;
+use crate::uihelp::registry::{self, WidgetSpec, REGISTRY};
 
-use ratatui::style::{Color, Style, Stylize};
-
-#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
 pub enum WidgetKind {
     Log,
     Data,
     Inventory,
     Player,
     Shotgun,
+    Confirm,
+    Clock,
+    Pause,
+    Title,
 }
 
 #[derive(Debug, Clone)]
 pub struct WidgetState {
-    pub display: bool,
-    pub focus: bool,
     area: Option<Rect>,
+    //set once the panel was moved or resized by hand, wins over the layout
+    floating: Option<Rect>,
     content: Option<String>,
     color: Option<Color>,
 }
 
 impl WidgetState {
-    pub fn new_blank() -> WidgetState {
+    /// The state a widget starts with, as declared in its [`WidgetSpec`].
+    pub fn from_spec(spec: &WidgetSpec) -> WidgetState {
         WidgetState {
-            display: false,
-            focus: false,
             area: None,
-            content: None,
-            color: Some(Color::White),
+            floating: None,
+            content: spec.content.map(String::from),
+            color: spec.color,
         }
     }
 
-    pub fn new_content(content: &str) -> WidgetState{
-        let content: String = content.to_string();
-        WidgetState {
-            display: true,
-            focus: true,
-            area: None,
-            content: Some(content),
-            color: Some(Color::White),
-        }
+    pub fn area(&self) -> Option<Rect> {
+        self.area
     }
 
-    fn change_state_content(&self, content: &str) -> WidgetState {
-        let content: String = content.to_string();
-        WidgetState {
-            display: self.display,
-            focus: self.focus,
-            area: self.area,
-            content: Some(content),
-            color: self.color,
-        }
+    pub fn content(&self) -> Option<&str> {
+        self.content.as_deref()
     }
 
-    pub fn new_color(color: Option<Color>) -> WidgetState{
-        WidgetState {
-            display: true,
-            focus: true,
-            area: None,
-            content: None,
-            color,
-        }
-    }
-
-    pub fn change_focus(&mut self) {
-        self.focus = !self.focus;
+    pub fn floating(&self) -> Option<Rect> {
+        self.floating
     }
 }
 
 #[derive(Debug, Clone)]
 pub struct WidgetData{
-    //these are a little redundant
-    log: RefCell<WidgetState>,
-    data: RefCell<WidgetState>,
-    inventory: RefCell<WidgetState>,
-    player: RefCell<WidgetState>,
-    shotgun: RefCell<WidgetState>,
-
-    current_focus: Option<WidgetKind>,
-    //render last in list first
-    pub render_stack: Vec<WidgetKind>,
+    states: HashMap<WidgetKind, RefCell<WidgetState>>,
+
+    //what is shown in which order and what has focus
+    focus: FocusManager,
+    //what the last frame drew, bottom first
+    draw_order: RefCell<Vec<WidgetKind>>,
+    //the area the last frame laid the widgets out in
+    bounds: Cell<Rect>,
+}
+
+impl Default for WidgetData {
+    fn default() -> Self {
+        Self::new()
+    }
 }
 
 impl WidgetData {
     pub fn new() -> WidgetData {
         WidgetData {
-            log: RefCell::new(WidgetState::new_blank()),
-            data: RefCell::new(WidgetState::new_color(Some(Color::Green))),
-            inventory: RefCell::new(WidgetState::new_blank()),
-            player: RefCell::new(WidgetState::new_blank()),
-            shotgun: RefCell::new(WidgetState::new_content(SHOTGUN_ART)),
-            current_focus: None,
-
-            render_stack: Vec::new(),
+            states: REGISTRY
+                .iter()
+                .map(|spec| (spec.kind, RefCell::new(WidgetState::from_spec(spec))))
+                .collect(),
+            focus: FocusManager::new(),
+            draw_order: RefCell::new(Vec::new()),
+            bounds: Cell::new(Rect::default()),
         }
     }
 
-    pub fn iter(&self) -> impl Iterator<Item = (WidgetKind, std::cell::Ref<WidgetState>)> {
-        [
-            (WidgetKind::Log, self.log.borrow()),
-            (WidgetKind::Data, self.data.borrow()),
-            (WidgetKind::Inventory, self.inventory.borrow()),
-            (WidgetKind::Player, self.player.borrow()),
-            (WidgetKind::Shotgun, self.shotgun.borrow()),
-        ]
-            .into_iter()
-    }
-
-    pub fn shown_widgets(&self) -> Option<WidgetKind> {
-        self.iter()
-            .find(|(_, state)| state.focus)
-            .map(|(kind, _)| kind)
+    pub fn iter(&self) -> impl Iterator<Item = (WidgetKind, Ref<'_, WidgetState>)> {
+        Self::order().map(|kind| (kind, self.get(kind).borrow()))
     }
 
-    fn order() -> [WidgetKind; 5] {
-        [
-            WidgetKind::Log,
-            WidgetKind::Data,
-            WidgetKind::Inventory,
-            WidgetKind::Player,
-            WidgetKind::Shotgun,
-        ]
+    fn order() -> impl Iterator<Item = WidgetKind> {
+        REGISTRY.iter().map(|spec| spec.kind)
     }
 
     fn get(&self, kind: WidgetKind) -> &RefCell<WidgetState> {
-        match kind {
-            WidgetKind::Log => &self.log,
-            WidgetKind::Data => &self.data,
-            WidgetKind::Inventory => &self.inventory,
-            WidgetKind::Player => &self.player,
-            WidgetKind::Shotgun => &self.shotgun,
-        }
+        self.states
+            .get(&kind)
+            .expect("every widget kind is registered")
     }
 
-    ///TODO: I want to understand this code
-    pub fn focus_next(&mut self) {
-        let order = Self::order();
-
-        let log_displayed = self.get(WidgetKind::Log).borrow().display;
+    pub fn is_displayed(&self, kind: WidgetKind) -> bool{
+        self.focus.is_open(kind)
+    }
 
-        // Find current focus index
-        let current_idx = order.iter().position(|&kind| self.get(kind).borrow_mut().focus);
+    pub fn is_focused(&self, kind: WidgetKind) -> bool{
+        self.focus.current() == Some(kind)
+    }
 
-        // Clear all focus
-        for kind in order.iter() {
-            self.get(*kind).borrow_mut().focus = false;
-        }
+    pub fn focused(&self) -> Option<WidgetKind> {
+        self.focus.current()
+    }
 
-        // Start searching from the next index
-        let mut next_idx = match current_idx {
-            Some(i) => (i + 1) % order.len(),
-            None => 0,
-        };
+    /// Shown widgets, bottom first.
+    pub fn stack(&self) -> &[WidgetKind] {
+        self.focus.stack()
+    }
 
-        // Loop until we find a displayed (and allowed) widget
-        for _ in 0..order.len() {
-            let kind = order[next_idx];
+    /// The modal widget that has taken over input, if one is open.
+    pub fn modal(&self) -> Option<WidgetKind> {
+        self.focus.modal()
+    }
 
-            // skip Shotgun if Log is displayed
-            if log_displayed && kind == WidgetKind::Shotgun {
-                next_idx = (next_idx + 1) % order.len();
-                continue;
-            }
+    /// Shows a widget on top and focuses it, returns false if a modal is in the way.
+    pub fn open(&mut self, kind: WidgetKind) -> bool {
+        self.focus.open(kind)
+    }
 
-            if self.get(kind).borrow().display {
-                self.get(kind).borrow_mut().focus = true;
-                return;
-            }
+    /// Hides a widget, focus returns to whatever had it before.
+    pub fn close(&mut self, kind: WidgetKind) {
+        self.focus.close(kind);
+    }
 
-            next_idx = (next_idx + 1) % order.len();
+    pub fn toggle(&mut self, kind: WidgetKind) {
+        if self.is_displayed(kind) {
+            self.close(kind);
+        } else {
+            self.open(kind);
         }
     }
 
-    pub fn focus_prev(&mut self) {
-        let order = Self::order();
+    /// Focuses a widget, movable ones come to the front.
+    pub fn focus(&mut self, kind: WidgetKind) -> bool {
+        self.focus.focus(kind)
+    }
 
-        // Find current focus index
-        let current_idx = order.iter().position(|&kind| self.get(kind).focus);
+    pub fn toggle_focus(&mut self, kind: WidgetKind) {
+        self.focus.toggle(kind);
+    }
 
-        // Clear all focus
-        for kind in order {
-            self.get_mut(kind).focus = false;
-        }
+    /// Tab order is the registry order, skipping hidden and unfocusable widgets.
+    pub fn cycle_focus(&mut self, forward: bool) {
+        let order: Vec<WidgetKind> = Self::order()
+            .filter(|kind| self.is_displayed(*kind) && registry::spec(*kind).focusable)
+            .collect();
+        self.focus.cycle(&order, forward);
+    }
 
-        // Start searching from the previous index
-        let mut prev_idx = match current_idx {
-            Some(0) => order.len() - 1,
-            Some(i) => i - 1,
-            None => order.len() - 1,
-        };
-
-        // Loop until we find a displayed widget
-        for _ in 0..order.len() {
-            if self.get(order[prev_idx]).display {
-                self.get_mut(order[prev_idx]).focus = true;
-                return;
-            }
-            prev_idx = if prev_idx == 0 { order.len() - 1 } else { prev_idx - 1 };
-        }
+    pub fn lower(&mut self, kind: WidgetKind) {
+        self.focus.lower(kind);
     }
 
-    pub fn is_displayed(&self, kind: WidgetKind) -> bool{
-        let widget_state = match kind {
-            WidgetKind::Log => &self.log,
-            WidgetKind::Data => &self.data,
-            WidgetKind::Inventory => &self.inventory,
-            WidgetKind::Player => &self.player,
-            WidgetKind::Shotgun => &self.shotgun,
-        };
-        widget_state.display
+    pub fn change_content(&self, kind: WidgetKind, content: Option<String>) {
+        self.get(kind).borrow_mut().content = content;
     }
 
-    pub fn is_focused(&self, kind: WidgetKind) -> bool{
-        let widget_state = match kind {
-            WidgetKind::Log => &self.log,
-            WidgetKind::Data => &self.data,
-            WidgetKind::Inventory => &self.inventory,
-            WidgetKind::Player => &self.player,
-            WidgetKind::Shotgun => &self.shotgun,
-        };
-        widget_state.focus
+    pub fn content(&self, kind: WidgetKind) -> Option<String> {
+        self.get(kind).borrow().content.clone()
     }
 
-    pub fn toggle_focus(&mut self, kind: WidgetKind) {
-        match kind {
-            WidgetKind::Log => self.log.focus = !self.log.focus,
-            WidgetKind::Data => self.data.focus = !self.data.focus,
-            WidgetKind::Inventory => self.inventory.focus = !self.inventory.focus,
-            WidgetKind::Player => self.player.focus = !self.player.focus,
-            WidgetKind::Shotgun => self.shotgun.focus = !self.shotgun.focus,
-        }
+    /// Remembers where the widget was last drawn, the layout is resolved on every frame.
+    pub fn set_area(&self, kind: WidgetKind, area: Option<Rect>) {
+        self.get(kind).borrow_mut().area = area;
+    }
 
-        if self.current_focus == Some(kind) {
-            self.current_focus = None;
-        }
-        else {
-            self.current_focus = Some(kind);
-        }
+    pub fn area(&self, kind: WidgetKind) -> Option<Rect> {
+        self.get(kind).borrow().area
     }
 
-    pub fn get_state(&self, kind: WidgetKind) -> Ref<WidgetState> {
-        match kind {
-            WidgetKind::Log => self.log.borrow(),
-            WidgetKind::Data => &self.data.borrow(),
-            WidgetKind::Inventory => &self.inventory.borrow(),
-            WidgetKind::Player => &self.player.borrow(),
-            WidgetKind::Shotgun => &self.shotgun.borrow(),
-        }
+    pub fn floating(&self, kind: WidgetKind) -> Option<Rect> {
+        self.get(kind).borrow().floating
     }
 
-    pub fn set_widget(&mut self, kind: WidgetKind, display_b: bool, focus_b: bool) {
-        if focus_b {
-            self.log.borrow_mut().focus = false;
-            self.data.borrow_mut().focus = false;
-            self.inventory.borrow_mut().focus = false;
-            self.player.borrow_mut().focus = false;
-            self.shotgun.borrow_mut().focus = false;
-        }
+    pub fn set_floating(&self, kind: WidgetKind, area: Option<Rect>) {
+        self.get(kind).borrow_mut().floating = area;
+    }
 
-        let widget_to_modify = match kind {
-            WidgetKind::Log => &mut self.log,
-            WidgetKind::Data => &mut self.data,
-            WidgetKind::Inventory => &mut self.inventory,
-            WidgetKind::Player => &mut self.player,
-            WidgetKind::Shotgun => &mut self.shotgun,
-        };
-        //_b means boolean
-        let mut widget = widget_to_modify.borrow_mut();
-        widget.display = display_b;
-        widget.focus = focus_b;
-        if focus_b {
-            self.current_focus = Some(kind);
-        }
+    pub fn bounds(&self) -> Rect {
+        self.bounds.get()
     }
 
-    pub fn change_content(&mut self, kind: WidgetKind, content: Option<String>) {
-        match kind {
-            WidgetKind::Log => self.log.borrow_mut().content = content,
-            WidgetKind::Data => self.data.borrow_mut().content = content,
-            WidgetKind::Inventory => self.inventory.borrow_mut().content = content,
-            WidgetKind::Shotgun => self.shotgun.borrow_mut().content = content,
-            WidgetKind::Player => self.player.borrow_mut().content = content,
-            _=> panic!("can't change content of invalid widget kind"),
-        }
+    pub fn set_bounds(&self, bounds: Rect) {
+        self.bounds.set(bounds);
     }
 
-    pub fn kind_focus(&mut self, kind: &WidgetKind){
-        self.log.borrow_mut().focus = false;
-        self.data.borrow_mut().focus = false;
-        self.inventory.borrow_mut().focus = false;
-        self.player.borrow_mut().focus = false;
-        self.shotgun.borrow_mut().focus = false;
+    pub fn set_draw_order(&self, order: Vec<WidgetKind>) {
+        *self.draw_order.borrow_mut() = order;
+    }
 
-        match kind {
-            WidgetKind::Log => self.log.borrow_mut().focus = true,
-            WidgetKind::Data => self.data.borrow_mut().focus = true,
-            WidgetKind::Inventory => self.inventory.borrow_mut().focus = true,
-            WidgetKind::Player => self.player.borrow_mut().focus = true,
-            WidgetKind::Shotgun => self.shotgun.borrow_mut().focus = true,
-        }
+    /// The topmost widget drawn at a screen position in the last frame.
+    pub fn widget_at(&self, column: u16, row: u16) -> Option<WidgetKind> {
+        self.draw_order
+            .borrow()
+            .iter()
+            .rev()
+            .copied()
+            .find(|kind| {
+                self.get(*kind)
+                    .borrow()
+                    .area
+                    .is_some_and(|area| area.contains((column, row).into()))
+            })
     }
 
     pub fn get_color(&self, kind: &WidgetKind) -> Option<Color> {
-        match kind {
-            WidgetKind::Log => self.log.borrow().color,
-            WidgetKind::Data => self.data.borrow().color,
-            WidgetKind::Inventory => self.inventory.borrow().color,
-            WidgetKind::Shotgun => self.shotgun.borrow().color,
-            WidgetKind::Player => self.player.borrow().color,
-        }
+        self.get(*kind).borrow().color
+    }
+
+    pub fn set_color(&self, kind: WidgetKind, color: Option<Color>) {
+        self.get(kind).borrow_mut().color = color;
     }
 }
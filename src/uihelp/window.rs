@@ -0,0 +1,144 @@
+//window.rs
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+
+use color_eyre::eyre::WrapErr;
+use ratatui::layout::Rect;
+use serde::{Deserialize, Serialize};
+
+use crate::uihelp::registry;
+use crate::uihelp::widget_data::{WidgetData, WidgetKind};
+
+/// Panels can't be resized smaller than this, so the border and title stay usable.
+pub const MIN_WIDTH: u16 = 12;
+pub const MIN_HEIGHT: u16 = 4;
+
+/// What a drag on a panel does.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DragMode {
+    /// grabbed by the title bar
+    Move,
+    /// grabbed by one or two edges, a corner grabs both
+    Resize { left: bool, right: bool, top: bool, bottom: bool },
+}
+
+/// A panel that is being dragged with the mouse.
+#[derive(Debug, Clone, Copy)]
+pub struct Drag {
+    pub kind: WidgetKind,
+    pub mode: DragMode,
+    start: (u16, u16),
+    origin: Rect,
+}
+
+impl Drag {
+    /// Starts a drag if the press landed on the border of `area`, `None` for the inside.
+    pub fn begin(kind: WidgetKind, area: Rect, column: u16, row: u16) -> Option<Drag> {
+        let left = column == area.x;
+        let right = column == area.right().saturating_sub(1);
+        let top = row == area.y;
+        let bottom = row == area.bottom().saturating_sub(1);
+
+        let mode = match (left, right, top, bottom) {
+            //the top border without the corners is the title bar
+            (false, false, true, false) => DragMode::Move,
+            (false, false, false, false) => return None,
+            (left, right, top, bottom) => DragMode::Resize { left, right, top, bottom },
+        };
+        Some(Drag { kind, mode, start: (column, row), origin: area })
+    }
+
+    /// Where the panel is with the cursor at `column`/`row`, kept inside `bounds`.
+    pub fn apply(&self, column: u16, row: u16, bounds: Rect) -> Rect {
+        let dx = column as i32 - self.start.0 as i32;
+        let dy = row as i32 - self.start.1 as i32;
+        let origin = self.origin;
+
+        let (mut x, mut y) = (origin.x as i32, origin.y as i32);
+        let (mut right, mut bottom) = (origin.right() as i32, origin.bottom() as i32);
+        match self.mode {
+            DragMode::Move => {
+                x += dx;
+                y += dy;
+                right += dx;
+                bottom += dy;
+            },
+            DragMode::Resize { left, right: r, top, bottom: b } => {
+                if left {
+                    x = (x + dx).min(right - MIN_WIDTH as i32);
+                }
+                if r {
+                    right = (right + dx).max(x + MIN_WIDTH as i32);
+                }
+                if top {
+                    y = (y + dy).min(bottom - MIN_HEIGHT as i32);
+                }
+                if b {
+                    bottom = (bottom + dy).max(y + MIN_HEIGHT as i32);
+                }
+            },
+        }
+
+        let rect = Rect {
+            x: x.max(0) as u16,
+            y: y.max(0) as u16,
+            width: (right - x).max(0) as u16,
+            height: (bottom - y).max(0) as u16,
+        };
+        fit(rect, bounds)
+    }
+}
+
+/// Shrinks and moves `rect` until it is inside `bounds`.
+pub fn fit(rect: Rect, bounds: Rect) -> Rect {
+    let width = rect.width.min(bounds.width);
+    let height = rect.height.min(bounds.height);
+    Rect {
+        x: rect.x.clamp(bounds.x, bounds.right() - width),
+        y: rect.y.clamp(bounds.y, bounds.bottom() - height),
+        width,
+        height,
+    }
+}
+
+/// Panel positions saved between sessions, as `[x, y, width, height]`.
+#[derive(Debug, Default, Serialize, Deserialize)]
+struct SavedLayout {
+    panels: HashMap<WidgetKind, [u16; 4]>,
+}
+
+/// `layout.toml` in the config directory.
+pub fn layout_path() -> Option<PathBuf> {
+    crate::settings::config_dir().map(|dir| dir.join("layout.toml"))
+}
+
+/// Restores panel positions, a missing file just means nothing was moved yet. Panels that can't
+/// be moved keep their docked place even if the file has one for them.
+pub fn load_layout(path: &Path, widget_data: &WidgetData) -> color_eyre::Result<()> {
+    if !path.exists() {
+        return Ok(());
+    }
+    let content = std::fs::read_to_string(path).wrap_err("could not read layout")?;
+    let saved: SavedLayout = toml::from_str(&content).wrap_err("invalid layout file")?;
+    for (kind, [x, y, width, height]) in saved.panels {
+        if !registry::spec(kind).movable {
+            continue;
+        }
+        widget_data.set_floating(kind, Some(Rect { x, y, width, height }));
+    }
+    Ok(())
+}
+
+/// Saves where the movable panels were put.
+pub fn save_layout(path: &Path, widget_data: &WidgetData) -> color_eyre::Result<()> {
+    let panels = widget_data
+        .iter()
+        .filter(|(kind, _)| registry::spec(*kind).movable)
+        .filter_map(|(kind, state)| state.floating().map(|r| (kind, [r.x, r.y, r.width, r.height])))
+        .collect();
+    let content = toml::to_string(&SavedLayout { panels })?;
+    if let Some(dir) = path.parent() {
+        std::fs::create_dir_all(dir)?;
+    }
+    std::fs::write(path, content).wrap_err("could not save layout")
+}
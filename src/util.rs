@@ -0,0 +1,9 @@
+//util.rs
+
+/// The option after `current` in `all`, wrapping around. An option that isn't in `all` starts
+/// from the first one.
+pub fn cycle<T: Copy + PartialEq>(all: &[T], current: T, forward: bool) -> T {
+    let idx = all.iter().position(|item| *item == current).unwrap_or(0);
+    let len = all.len();
+    all[if forward { (idx + 1) % len } else { (idx + len - 1) % len }]
+}